@@ -10,7 +10,7 @@ fn main() {
     let mut request = Request::new(Command::Set);
     request.set_key(Some(b"Hello"));
     request.set_value(Some(b"World"));
-    // request.set_extras(Some(extras));
+    request.set_extras(Some(extras)).expect("extras are valid for the Set command");
 
     println!("{:#?}", request);
 }
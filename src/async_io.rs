@@ -0,0 +1,321 @@
+use std::io;
+use std::fmt;
+use std::mem;
+use std::pin::Pin;
+use std::future::Future;
+use std::task::{Context, Poll};
+
+use bytes::BytesMut;
+use futures_io::{AsyncRead, AsyncWrite};
+use futures_core::Stream;
+use futures_sink::Sink;
+
+use super::{Request, Response, ResponseDecoder, Error};
+use super::response::Header;
+use super::framing::{self, HEADER_SIZE, CHUNK_SIZE};
+
+impl Request {
+
+    /// Write serialized request into the `futures-io` writer.
+    ///
+    /// Async counterpart of the [Request::write_to](#method.write_to),
+    /// available with the `futures` feature enabled.
+    ///
+    /// Request is serialized immediately, so returned future does not borrow it.
+    pub fn write_async<'a, W: AsyncWrite + Unpin>(&self, out: &'a mut W) -> WriteRequest<'a, W> {
+        let mut buf = Vec::with_capacity(self.len());
+        let error = self.write(&mut buf).err();
+
+        WriteRequest {
+            out: out,
+            buf: buf,
+            written: 0,
+            error: error,
+        }
+    }
+}
+
+impl Response {
+
+    /// Read a single response from the `futures-io` reader.
+    ///
+    /// Async counterpart of the [Response::read_from](#method.read_from),
+    /// available with the `futures` feature enabled.
+    ///
+    /// Header is validated as soon as it is received, before the body is read.
+    /// Resolves to `Ok(None)` if `src` is at EOF before the first header byte.
+    pub fn read_async<'a, R: AsyncRead + Unpin>(src: &'a mut R) -> ReadResponse<'a, R> {
+        ReadResponse {
+            src: src,
+            packet: Vec::with_capacity(HEADER_SIZE),
+            filled: 0,
+            length: None,
+        }
+    }
+}
+
+/// Future returned by [Request::write_async](struct.Request.html#method.write_async).
+pub struct WriteRequest<'a, W: 'a> {
+    out: &'a mut W,
+    buf: Vec<u8>,
+    written: usize,
+    error: Option<Error>,
+}
+
+impl<'a, W: AsyncWrite + Unpin> Future for WriteRequest<'a, W> {
+    type Output = Result<(), Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if let Some(error) = this.error.take() {
+            return Poll::Ready(Err(error));
+        }
+
+        poll_write_all(&mut *this.out, cx, &this.buf, &mut this.written)
+    }
+}
+
+impl<'a, W> fmt::Debug for WriteRequest<'a, W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WriteRequest")
+            .field("length", &self.buf.len())
+            .field("written", &self.written)
+            .finish()
+    }
+}
+
+/// Future returned by [Response::read_async](struct.Response.html#method.read_async).
+pub struct ReadResponse<'a, R: 'a> {
+    src: &'a mut R,
+    packet: Vec<u8>,
+    filled: usize,
+    // Full packet length, known as soon as header is parsed
+    length: Option<usize>,
+}
+
+impl<'a, R: AsyncRead + Unpin> Future for ReadResponse<'a, R> {
+    type Output = Result<Option<Response>, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            let target = this.length.unwrap_or(HEADER_SIZE);
+            if this.filled == target {
+                match this.length {
+                    None => {
+                        match Header::read(&this.packet[..HEADER_SIZE]) {
+                            Ok(header) => this.length = Some(header.packet_length()),
+                            Err(e) => return Poll::Ready(Err(e)),
+                        }
+                        continue;
+                    },
+                    Some(..) => {
+                        let mut packet = BytesMut::from(mem::replace(&mut this.packet, Vec::new()));
+                        return Poll::Ready(Response::read_bytes(&mut packet));
+                    },
+                }
+            }
+
+            framing::grow_buffer(&mut this.packet, this.filled, target);
+
+            match Pin::new(&mut *this.src).poll_read(cx, &mut this.packet[this.filled..]) {
+                Poll::Ready(Ok(0)) if this.filled == 0 => return Poll::Ready(Ok(None)),
+                Poll::Ready(Ok(0)) => return Poll::Ready(Err(framing::truncated(this.filled, target))),
+                Poll::Ready(Ok(n)) => this.filled += n,
+                Poll::Ready(Err(ref e)) if e.kind() == io::ErrorKind::Interrupted => {},
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(Error::Io(e))),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<'a, R> fmt::Debug for ReadResponse<'a, R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ReadResponse")
+            .field("filled", &self.filled)
+            .field("length", &self.length)
+            .finish()
+    }
+}
+
+/// Client side `Stream`/`Sink` adapter for the `futures-io` transports.
+///
+/// Sends [Request](struct.Request.html)s and receives [Response](struct.Response.html)s,
+/// so it can be used with any runtime which provides `AsyncRead`/`AsyncWrite` streams.
+///
+/// Requests are buffered until the sink is flushed,
+/// responses are decoded incrementally, the same way as by the
+/// [ResponseDecoder](struct.ResponseDecoder.html).
+///
+/// Stream is finished after the first decoding or I/O error,
+/// buffered data is dropped, since the connection can not be trusted anymore.
+///
+/// Available with the `futures` feature enabled.
+pub struct ClientTransport<T> {
+    io: T,
+    decoder: ResponseDecoder,
+    read_buf: BytesMut,
+    write_buf: Vec<u8>,
+    written: usize,
+    finished: bool,
+}
+
+impl<T> ClientTransport<T> {
+
+    pub fn new(io: T) -> ClientTransport<T> {
+        ClientTransport {
+            io: io,
+            decoder: ResponseDecoder::new(),
+            read_buf: BytesMut::new(),
+            write_buf: Vec::new(),
+            written: 0,
+            finished: false,
+        }
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.io
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.io
+    }
+
+    /// Consume transport and return the underlying I/O object.
+    ///
+    /// Buffered data, which was not sent or decoded yet, is lost.
+    pub fn into_inner(self) -> T {
+        self.io
+    }
+}
+
+impl<T: AsyncRead + Unpin> Stream for ClientTransport<T> {
+    type Item = Result<Response, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let mut chunk = [0; CHUNK_SIZE];
+
+        if this.finished {
+            return Poll::Ready(None);
+        }
+
+        loop {
+            match this.decoder.decode(&mut this.read_buf) {
+                Ok(Some(response)) => return Poll::Ready(Some(Ok(response))),
+                Ok(None) => {},
+                Err(e) => return Poll::Ready(Some(Err(this.finish(e)))),
+            }
+
+            match Pin::new(&mut this.io).poll_read(cx, &mut chunk) {
+                Poll::Ready(Ok(0)) => {
+                    if this.read_buf.is_empty() && !this.decoder.is_pending() {
+                        this.finished = true;
+                        return Poll::Ready(None);
+                    }
+
+                    return Poll::Ready(Some(Err(this.finish(Error::Io(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "stream ended in the middle of a packet",
+                    ))))));
+                },
+                Poll::Ready(Ok(n)) => this.read_buf.extend_from_slice(&chunk[..n]),
+                Poll::Ready(Err(ref e)) if e.kind() == io::ErrorKind::Interrupted => {},
+                Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(this.finish(Error::Io(e))))),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<T> ClientTransport<T> {
+    // Reset the read state, so the stream is finished after this error
+    fn finish(&mut self, error: Error) -> Error {
+        self.read_buf.clear();
+        self.decoder = ResponseDecoder::new();
+        self.finished = true;
+        error
+    }
+}
+
+impl<T: AsyncWrite + Unpin> ClientTransport<T> {
+    fn poll_write_buf(&mut self, cx: &mut Context) -> Poll<Result<(), Error>> {
+        match poll_write_all(&mut self.io, cx, &self.write_buf, &mut self.written) {
+            Poll::Ready(Ok(())) => {
+                self.write_buf.clear();
+                self.written = 0;
+                Poll::Ready(Ok(()))
+            },
+            other => other,
+        }
+    }
+}
+
+impl<T: AsyncWrite + Unpin> Sink<Request> for ClientTransport<T> {
+    type Error = Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+        let this = self.get_mut();
+        if this.write_buf.len() >= CHUNK_SIZE {
+            return this.poll_write_buf(cx);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Request) -> Result<(), Error> {
+        item.write(&mut self.get_mut().write_buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+        let this = self.get_mut();
+        match this.poll_write_buf(cx) {
+            Poll::Ready(Ok(())) => {},
+            other => return other,
+        }
+
+        Pin::new(&mut this.io).poll_flush(cx).map_err(Error::Io)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Error>> {
+        let this = self.get_mut();
+        match this.poll_write_buf(cx) {
+            Poll::Ready(Ok(())) => {},
+            other => return other,
+        }
+
+        Pin::new(&mut this.io).poll_close(cx).map_err(Error::Io)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ClientTransport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ClientTransport")
+            .field("io", &self.io)
+            .field("decoder", &self.decoder)
+            .field("read_buffered", &self.read_buf.len())
+            .field("write_buffered", &(self.write_buf.len() - self.written))
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
+
+fn poll_write_all<W: AsyncWrite + Unpin>(out: &mut W, cx: &mut Context, buf: &[u8], written: &mut usize)
+        -> Poll<Result<(), Error>> {
+    while *written < buf.len() {
+        match Pin::new(&mut *out).poll_write(cx, &buf[*written..]) {
+            Poll::Ready(Ok(0)) => return Poll::Ready(Err(Error::Io(io::Error::new(
+                io::ErrorKind::WriteZero,
+                "failed to write the whole packet",
+            )))),
+            Poll::Ready(Ok(n)) => *written += n,
+            Poll::Ready(Err(ref e)) if e.kind() == io::ErrorKind::Interrupted => {},
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(Error::Io(e))),
+            Poll::Pending => return Poll::Pending,
+        }
+    }
+
+    Poll::Ready(Ok(()))
+}
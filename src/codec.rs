@@ -0,0 +1,179 @@
+use std::fmt;
+use std::default::Default;
+
+use bytes::BytesMut;
+use tokio_codec::{Encoder, Decoder};
+
+use super::{Request, Response, ResponseDecoder, Error, DEFAULT_MAX_BODY_LENGTH};
+use super::framing::{self, HEADER_SIZE};
+
+/// Client side codec for the `tokio` framed transports.
+///
+/// Encodes [Request](struct.Request.html)s and decodes [Response](struct.Response.html)s,
+/// partial responses are kept in the read buffer until the rest of the packet arrives.
+///
+/// Available with the `tokio` feature enabled.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate bytes;
+/// extern crate tokio_codec;
+/// extern crate memcache_proto;
+///
+/// use bytes::BytesMut;
+/// use tokio_codec::{Encoder, Decoder};
+/// use memcache_proto::{Request, MemcacheClientCodec};
+///
+/// # fn main() {
+/// let mut codec = MemcacheClientCodec::new();
+/// let mut buf = BytesMut::new();
+/// codec.encode(Request::noop(), &mut buf).unwrap();
+/// assert_eq!(buf.len(), 24);
+///
+/// // Not a complete response yet
+/// let mut buf = BytesMut::from(&[0x81, 0x0a][..]);
+/// assert!(codec.decode(&mut buf).unwrap().is_none());
+/// # }
+/// ```
+pub struct MemcacheClientCodec {
+    decoder: ResponseDecoder,
+}
+
+impl MemcacheClientCodec {
+    pub fn new() -> MemcacheClientCodec {
+        MemcacheClientCodec {
+            decoder: ResponseDecoder::new(),
+        }
+    }
+}
+
+impl Encoder for MemcacheClientCodec {
+    type Item = Request;
+    type Error = Error;
+
+    fn encode(&mut self, item: Request, dst: &mut BytesMut) -> Result<(), Error> {
+        dst.reserve(item.len());
+        item.write(dst)
+    }
+}
+
+impl Decoder for MemcacheClientCodec {
+    type Item = Response;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Response>, Error> {
+        self.decoder.decode(src)
+    }
+}
+
+impl Default for MemcacheClientCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for MemcacheClientCodec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MemcacheClientCodec")
+            .field("decoder", &self.decoder)
+            .finish()
+    }
+}
+
+/// Server side codec for the `tokio` framed transports.
+///
+/// Decodes [Request](struct.Request.html)s and encodes [Response](struct.Response.html)s.
+///
+/// Request header is validated as soon as it is received: packets with a non-request magic byte
+/// or with a body longer than the configured limit are rejected before the body is buffered.
+///
+/// Available with the `tokio` feature enabled.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate bytes;
+/// extern crate tokio_codec;
+/// extern crate memcache_proto;
+///
+/// use bytes::BytesMut;
+/// use tokio_codec::Decoder;
+/// use memcache_proto::{Request, Command, MemcacheServerCodec};
+///
+/// # fn main() {
+/// let mut raw: Vec<u8> = vec![];
+/// Request::get(b"Hello").write(&mut raw).unwrap();
+///
+/// let mut codec = MemcacheServerCodec::with_max_body_length(512);
+/// let mut buf = BytesMut::from(raw);
+/// let request = codec.decode(&mut buf).unwrap().unwrap();
+/// assert_eq!(*request.command(), Command::Get);
+/// # }
+/// ```
+pub struct MemcacheServerCodec {
+    max_body_length: usize,
+}
+
+impl MemcacheServerCodec {
+    /// Create codec with the [DEFAULT_MAX_BODY_LENGTH](constant.DEFAULT_MAX_BODY_LENGTH.html) limit.
+    pub fn new() -> MemcacheServerCodec {
+        MemcacheServerCodec::with_max_body_length(DEFAULT_MAX_BODY_LENGTH)
+    }
+
+    /// Create codec, which rejects requests with a body longer than `max_body_length` bytes.
+    pub fn with_max_body_length(max_body_length: usize) -> MemcacheServerCodec {
+        MemcacheServerCodec {
+            max_body_length: max_body_length,
+        }
+    }
+
+    pub fn max_body_length(&self) -> usize {
+        self.max_body_length
+    }
+}
+
+impl Encoder for MemcacheServerCodec {
+    type Item = Response;
+    type Error = Error;
+
+    fn encode(&mut self, item: Response, dst: &mut BytesMut) -> Result<(), Error> {
+        dst.reserve(item.len());
+        item.write(dst)
+    }
+}
+
+impl Decoder for MemcacheServerCodec {
+    type Item = Request;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Request>, Error> {
+        if src.len() < HEADER_SIZE {
+            return Ok(None);
+        }
+
+        let body_length = framing::check_request_header(&src[..HEADER_SIZE], self.max_body_length)?;
+        let packet_length = HEADER_SIZE + body_length;
+        if src.len() < packet_length {
+            src.reserve(packet_length - src.len());
+            return Ok(None);
+        }
+
+        let packet = src.split_to(packet_length);
+        Request::read(&packet)
+    }
+}
+
+impl Default for MemcacheServerCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for MemcacheServerCodec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MemcacheServerCodec")
+            .field("max_body_length", &self.max_body_length)
+            .finish()
+    }
+}
@@ -1,67 +1,387 @@
-enum_from_primitive! {
+/// Available protocol commands
+///
+/// Unknown opcodes are preserved as `Command::Unknown`,
+/// so packets of the newer protocol versions can still be decoded and forwarded.
+///
+/// Reference: https://github.com/memcached/memcached/wiki/BinaryProtocolRevamped#command-opcodes
+#[derive(Debug, PartialEq, Copy, Clone)]
+#[non_exhaustive]
+pub enum Command {
+    Get,
+    Set,
+    Add,
+    Replace,
+    Delete,
+    Increment,
+    Decrement,
+    Quit,
+    Flush,
+    GetQ,
+    Noop,
+    Version,
+    GetK,
+    GetKQ,
+    Append,
+    Prepend,
+    Stat,
+    SetQ,
+    AddQ,
+    ReplaceQ,
+    DeleteQ,
+    IncrementQ,
+    DecrementQ,
+    QuitQ,
+    FlushQ,
+    AppendQ,
+    PrependQ,
+    Verbosity,
+    Touch,
+    Gat,
+    GatQ,
+    SaslListMechs,
+    SaslAuth,
+    SaslStep,
+    RGet,
+    RSet,
+    RSetQ,
+    RAppend,
+    RAppendQ,
+    RPrepend,
+    RPrependQ,
+    RDelete,
+    RDeleteQ,
+    RIncr,
+    RIncrQ,
+    RDecr,
+    RDecrQ,
+    SetVBucket,
+    GetVBucket,
+    DelVBucket,
+    TapConnect,
+    TapMutation,
+    TapDelete,
+    TapFlush,
+    TapOpaque,
+    TapVBucketSet,
+    TapCheckpointStart,
+    TapCheckpointEnd,
+    /// Opcode, which is not known to this crate.
+    ///
+    /// Metadata of such commands is unknown as well, so packets are validated loosely.
+    ///
+    /// It should hold only unassigned opcodes: `Command::Unknown(0x1c)` is sent as `Touch`,
+    /// but does not compare equal to `Command::Touch`. Use `Command::from(opcode)`
+    /// for the raw values, request and response constructors are converting such commands
+    /// into the known ones.
+    Unknown(u8),
+}
+
+impl Command {
+
+    /// Check if server should not respond to the command in case of success.
+    pub fn is_quiet(&self) -> bool {
+        match *self {
+            Command::GetQ | Command::GetKQ | Command::SetQ | Command::AddQ | Command::ReplaceQ |
+            Command::DeleteQ | Command::IncrementQ | Command::DecrementQ | Command::QuitQ |
+            Command::FlushQ | Command::AppendQ | Command::PrependQ | Command::GatQ |
+            Command::RSetQ | Command::RAppendQ | Command::RPrependQ | Command::RDeleteQ |
+            Command::RIncrQ | Command::RDecrQ => true,
+            _ => false,
+        }
+    }
 
-    /// Available protocol commands
+    /// Known command for the `Command::Unknown` with an assigned opcode, other commands are kept as is.
+    pub(crate) fn normalize(self) -> Command {
+        Command::from(u8::from(self))
+    }
+
+    /// Quiet counterpart of the command.
+    ///
+    /// Quiet commands are returned as is, `None` is returned
+    /// if command has no quiet version.
+    ///
+    /// # Examples
     ///
-    /// Reference: https://github.com/memcached/memcached/wiki/BinaryProtocolRevamped#command-opcodes
-    #[derive(Debug, PartialEq, Copy, Clone)]
-    pub enum Command {
-        Get = 0x00,
-        Set = 0x01,
-        Add = 0x02,
-        Replace = 0x03,
-        Delete = 0x04,
-        Increment = 0x05,
-        Decrement = 0x06,
-        Quit = 0x07,
-        Flush = 0x08,
-        GetQ = 0x09,
-        Noop = 0x0a,
-        Version = 0x0b,
-        GetK = 0x0c,
-        GetKQ = 0x0d,
-        Append = 0x0e,
-        Prepend = 0x0f,
-        Stat = 0x10,
-        SetQ = 0x11,
-        AddQ = 0x12,
-        ReplaceQ = 0x13,
-        DeleteQ = 0x14,
-        IncrementQ = 0x15,
-        DecrementQ = 0x16,
-        QuitQ = 0x17,
-        FlushQ = 0x18,
-        AppendQ = 0x19,
-        PrependQ = 0x1a,
-        Verbosity = 0x1b,
-        Touch = 0x1c,
-        Gat = 0x1d,
-        GatQ = 0x1e,
-        SaslListMechs = 0x20,
-        SaslAuth = 0x21,
-        SaslStep = 0x22,
-        RGet = 0x30,
-        RSet = 0x31,
-        RSetQ = 0x32,
-        RAppend = 0x33,
-        RAppendQ = 0x34,
-        RPrepend = 0x35,
-        RPrependQ = 0x36,
-        RDelete = 0x37,
-        RDeleteQ = 0x38,
-        RIncr = 0x39,
-        RIncrQ = 0x3a,
-        RDecr = 0x3b,
-        RDecrQ = 0x3c,
-        SetVBucket = 0x3d,
-        GetVBucket = 0x3e,
-        DelVBucket = 0x3f,
-        TapConnect = 0x40,
-        TapMutation = 0x41,
-        TapDelete = 0x42,
-        TapFlush = 0x43,
-        TapOpaque = 0x44,
-        TapVBucketSet = 0x45,
-        TapCheckpointStart = 0x46,
-        TapCheckpointEnd = 0x47,
+    /// ```rust
+    /// use memcache_proto::Command;
+    ///
+    /// assert_eq!(Command::GetK.quiet(), Some(Command::GetKQ));
+    /// assert_eq!(Command::GetKQ.quiet(), Some(Command::GetKQ));
+    /// assert_eq!(Command::Noop.quiet(), None);
+    /// ```
+    pub fn quiet(&self) -> Option<Command> {
+        let command = match *self {
+            Command::Get => Command::GetQ,
+            Command::GetK => Command::GetKQ,
+            Command::Set => Command::SetQ,
+            Command::Add => Command::AddQ,
+            Command::Replace => Command::ReplaceQ,
+            Command::Delete => Command::DeleteQ,
+            Command::Increment => Command::IncrementQ,
+            Command::Decrement => Command::DecrementQ,
+            Command::Quit => Command::QuitQ,
+            Command::Flush => Command::FlushQ,
+            Command::Append => Command::AppendQ,
+            Command::Prepend => Command::PrependQ,
+            Command::Gat => Command::GatQ,
+            Command::RSet => Command::RSetQ,
+            Command::RAppend => Command::RAppendQ,
+            Command::RPrepend => Command::RPrependQ,
+            Command::RDelete => Command::RDeleteQ,
+            Command::RIncr => Command::RIncrQ,
+            Command::RDecr => Command::RDecrQ,
+            command if command.is_quiet() => command,
+            _ => return None,
+        };
+
+        Some(command)
+    }
+
+    /// Non-quiet counterpart of the command.
+    ///
+    /// Every command has a non-quiet version, non-quiet commands are returned as is.
+    pub fn loud(&self) -> Command {
+        match *self {
+            Command::GetQ => Command::Get,
+            Command::GetKQ => Command::GetK,
+            Command::SetQ => Command::Set,
+            Command::AddQ => Command::Add,
+            Command::ReplaceQ => Command::Replace,
+            Command::DeleteQ => Command::Delete,
+            Command::IncrementQ => Command::Increment,
+            Command::DecrementQ => Command::Decrement,
+            Command::QuitQ => Command::Quit,
+            Command::FlushQ => Command::Flush,
+            Command::AppendQ => Command::Append,
+            Command::PrependQ => Command::Prepend,
+            Command::GatQ => Command::Gat,
+            Command::RSetQ => Command::RSet,
+            Command::RAppendQ => Command::RAppend,
+            Command::RPrependQ => Command::RPrepend,
+            Command::RDeleteQ => Command::RDelete,
+            Command::RIncrQ => Command::RIncr,
+            Command::RDecrQ => Command::RDecr,
+            command => command,
+        }
+    }
+
+    /// Check if request must have a key.
+    pub fn requires_key(&self) -> bool {
+        match self.loud() {
+            Command::Get | Command::GetK | Command::Set | Command::Add | Command::Replace |
+            Command::Delete | Command::Increment | Command::Decrement | Command::Append |
+            Command::Prepend | Command::Touch | Command::Gat | Command::SaslAuth |
+            Command::SaslStep | Command::RGet | Command::RSet | Command::RAppend |
+            Command::RPrepend | Command::RDelete | Command::RIncr | Command::RDecr => true,
+            _ => false,
+        }
+    }
+
+    /// Check if request is allowed to have a value.
+    pub fn allows_value(&self) -> bool {
+        match self.loud() {
+            Command::Set | Command::Add | Command::Replace | Command::Append | Command::Prepend |
+            Command::SaslAuth | Command::SaslStep |
+            Command::RGet | Command::RSet | Command::RAppend | Command::RPrepend |
+            Command::RDelete | Command::RIncr | Command::RDecr |
+            Command::TapConnect | Command::TapMutation | Command::TapDelete | Command::TapFlush |
+            Command::TapOpaque | Command::TapVBucketSet |
+            Command::TapCheckpointStart | Command::TapCheckpointEnd |
+            Command::Unknown(..) => true,
+            _ => false,
+        }
+    }
+
+    /// Required request extras length.
+    ///
+    /// `None` is returned if length is variable, ex. for `Flush` with optional expiration.
+    pub fn request_extras_len(&self) -> Option<usize> {
+        let length = match self.loud() {
+            Command::Set | Command::Add | Command::Replace => 8,
+            Command::Increment | Command::Decrement => 20,
+            Command::Verbosity | Command::Touch | Command::Gat => 4,
+            Command::RGet | Command::RSet | Command::RAppend | Command::RPrepend |
+            Command::RDelete | Command::RIncr | Command::RDecr => 8,
+            Command::SetVBucket | Command::TapConnect => 4,
+            Command::Flush | Command::TapMutation | Command::TapDelete | Command::TapFlush |
+            Command::TapOpaque | Command::TapVBucketSet |
+            Command::TapCheckpointStart | Command::TapCheckpointEnd |
+            Command::Unknown(..) => return None,
+            _ => 0,
+        };
+
+        Some(length)
+    }
+
+    /// Extras length of the successful response.
+    ///
+    /// `None` is returned if length is variable.
+    pub fn response_extras_len(&self) -> Option<usize> {
+        let length = match self.loud() {
+            // memcached answers Touch through the Gat path, so the flags are sent back too
+            Command::Get | Command::GetK | Command::Touch | Command::Gat => 4,
+            Command::RGet | Command::RSet | Command::RAppend | Command::RPrepend |
+            Command::RDelete | Command::RIncr | Command::RDecr |
+            Command::TapConnect | Command::TapMutation | Command::TapDelete | Command::TapFlush |
+            Command::TapOpaque | Command::TapVBucketSet |
+            Command::TapCheckpointStart | Command::TapCheckpointEnd |
+            Command::Unknown(..) => return None,
+            _ => 0,
+        };
+
+        Some(length)
+    }
+
+    /// Value length of the successful response, if it is fixed.
+    ///
+    /// Ex. `Increment` responses are always carrying an 8-byte counter.
+    pub fn response_value_len(&self) -> Option<usize> {
+        match self.loud() {
+            Command::Increment | Command::Decrement => Some(8),
+            Command::GetVBucket => Some(4),
+            _ => None,
+        }
+    }
+}
+
+/// Conversion from the raw opcode, unknown values are kept as `Command::Unknown`.
+///
+/// # Examples
+///
+/// ```rust
+/// use memcache_proto::Command;
+///
+/// assert_eq!(Command::from(0x0a), Command::Noop);
+/// assert_eq!(Command::from(0x1f), Command::Unknown(0x1f));
+/// ```
+impl From<u8> for Command {
+    fn from(opcode: u8) -> Command {
+        match opcode {
+            0x00 => Command::Get,
+            0x01 => Command::Set,
+            0x02 => Command::Add,
+            0x03 => Command::Replace,
+            0x04 => Command::Delete,
+            0x05 => Command::Increment,
+            0x06 => Command::Decrement,
+            0x07 => Command::Quit,
+            0x08 => Command::Flush,
+            0x09 => Command::GetQ,
+            0x0a => Command::Noop,
+            0x0b => Command::Version,
+            0x0c => Command::GetK,
+            0x0d => Command::GetKQ,
+            0x0e => Command::Append,
+            0x0f => Command::Prepend,
+            0x10 => Command::Stat,
+            0x11 => Command::SetQ,
+            0x12 => Command::AddQ,
+            0x13 => Command::ReplaceQ,
+            0x14 => Command::DeleteQ,
+            0x15 => Command::IncrementQ,
+            0x16 => Command::DecrementQ,
+            0x17 => Command::QuitQ,
+            0x18 => Command::FlushQ,
+            0x19 => Command::AppendQ,
+            0x1a => Command::PrependQ,
+            0x1b => Command::Verbosity,
+            0x1c => Command::Touch,
+            0x1d => Command::Gat,
+            0x1e => Command::GatQ,
+            0x20 => Command::SaslListMechs,
+            0x21 => Command::SaslAuth,
+            0x22 => Command::SaslStep,
+            0x30 => Command::RGet,
+            0x31 => Command::RSet,
+            0x32 => Command::RSetQ,
+            0x33 => Command::RAppend,
+            0x34 => Command::RAppendQ,
+            0x35 => Command::RPrepend,
+            0x36 => Command::RPrependQ,
+            0x37 => Command::RDelete,
+            0x38 => Command::RDeleteQ,
+            0x39 => Command::RIncr,
+            0x3a => Command::RIncrQ,
+            0x3b => Command::RDecr,
+            0x3c => Command::RDecrQ,
+            0x3d => Command::SetVBucket,
+            0x3e => Command::GetVBucket,
+            0x3f => Command::DelVBucket,
+            0x40 => Command::TapConnect,
+            0x41 => Command::TapMutation,
+            0x42 => Command::TapDelete,
+            0x43 => Command::TapFlush,
+            0x44 => Command::TapOpaque,
+            0x45 => Command::TapVBucketSet,
+            0x46 => Command::TapCheckpointStart,
+            0x47 => Command::TapCheckpointEnd,
+            opcode => Command::Unknown(opcode),
+        }
+    }
+}
+
+impl From<Command> for u8 {
+    fn from(command: Command) -> u8 {
+        match command {
+            Command::Get => 0x00,
+            Command::Set => 0x01,
+            Command::Add => 0x02,
+            Command::Replace => 0x03,
+            Command::Delete => 0x04,
+            Command::Increment => 0x05,
+            Command::Decrement => 0x06,
+            Command::Quit => 0x07,
+            Command::Flush => 0x08,
+            Command::GetQ => 0x09,
+            Command::Noop => 0x0a,
+            Command::Version => 0x0b,
+            Command::GetK => 0x0c,
+            Command::GetKQ => 0x0d,
+            Command::Append => 0x0e,
+            Command::Prepend => 0x0f,
+            Command::Stat => 0x10,
+            Command::SetQ => 0x11,
+            Command::AddQ => 0x12,
+            Command::ReplaceQ => 0x13,
+            Command::DeleteQ => 0x14,
+            Command::IncrementQ => 0x15,
+            Command::DecrementQ => 0x16,
+            Command::QuitQ => 0x17,
+            Command::FlushQ => 0x18,
+            Command::AppendQ => 0x19,
+            Command::PrependQ => 0x1a,
+            Command::Verbosity => 0x1b,
+            Command::Touch => 0x1c,
+            Command::Gat => 0x1d,
+            Command::GatQ => 0x1e,
+            Command::SaslListMechs => 0x20,
+            Command::SaslAuth => 0x21,
+            Command::SaslStep => 0x22,
+            Command::RGet => 0x30,
+            Command::RSet => 0x31,
+            Command::RSetQ => 0x32,
+            Command::RAppend => 0x33,
+            Command::RAppendQ => 0x34,
+            Command::RPrepend => 0x35,
+            Command::RPrependQ => 0x36,
+            Command::RDelete => 0x37,
+            Command::RDeleteQ => 0x38,
+            Command::RIncr => 0x39,
+            Command::RIncrQ => 0x3a,
+            Command::RDecr => 0x3b,
+            Command::RDecrQ => 0x3c,
+            Command::SetVBucket => 0x3d,
+            Command::GetVBucket => 0x3e,
+            Command::DelVBucket => 0x3f,
+            Command::TapConnect => 0x40,
+            Command::TapMutation => 0x41,
+            Command::TapDelete => 0x42,
+            Command::TapFlush => 0x43,
+            Command::TapOpaque => 0x44,
+            Command::TapVBucketSet => 0x45,
+            Command::TapCheckpointStart => 0x46,
+            Command::TapCheckpointEnd => 0x47,
+            Command::Unknown(opcode) => opcode,
+        }
     }
-}
\ No newline at end of file
+}
@@ -1,46 +1,113 @@
 use std::error;
-use std::error::Error;
 use std::io;
 use std::fmt;
 use std::convert;
 
-pub enum ResponseError {
-    UnknownMagic,
-    UnknownCommand,
-    UnknownDataType,
-    UnknownStatus,
-    InvalidData(io::Error),
-}
+use super::{Magic, Command, Status};
+use super::extras::Expiry;
 
-impl error::Error for ResponseError {
-    fn description(&self) -> &str {
-        unimplemented!()
-    }
+/// Errors which might happen while encoding or decoding protocol packets.
+///
+/// Non-`Ok` response statuses are also converted into this type,
+/// so it can be used as a single error type for the whole client or server code.
+#[derive(Debug)]
+pub enum Error {
+    /// Packet starts with an unknown magic byte.
+    UnknownMagic(u8),
+    /// Packet magic byte is known, but is not expected here,
+    /// ex. response packet was received instead of request.
+    UnexpectedMagic(Magic),
+    /// Packet data type is not a known one.
+    UnknownDataType(u8),
+    /// vBucket state is not a known one.
+    UnknownVBucketState(u32),
+    /// Extras and key lengths are exceeding total body length.
+    InvalidBodyLength(usize),
+    /// Extras type can't be used with this command.
+    InvalidExtras(Command),
+    /// Packet command does not match the requested data.
+    UnexpectedCommand(Command),
+    /// Packet part length does not match the expected one.
+    InvalidLength {
+        expected: usize,
+        actual: usize,
+    },
+    /// Request command requires a key, but it is missing.
+    MissingKey(Command),
+    /// Request command does not allow a value, but it was provided.
+    UnexpectedValue(Command),
+    /// Key is longer than allowed.
+    KeyTooLong(usize),
+    /// Extras are longer than 255 bytes.
+    ExtrasTooLong(usize),
+    /// Total body length does not fit into `u32`.
+    BodyTooLong(usize),
+    /// Received body length exceeds the configured limit.
+    BodyLimitExceeded {
+        length: usize,
+        limit: usize,
+    },
+    /// Output buffer is not able to fit the whole packet.
+    NotEnoughCapacity {
+        required: usize,
+        remaining: usize,
+    },
+    /// Expiration can't be represented in the protocol seconds value.
+    InvalidExpiration(Expiry),
+    /// Server responded with a non-`Ok` status.
+    Status(Status),
+    /// I/O error happened while reading or writing packet parts.
+    Io(io::Error),
+}
 
-    fn cause(&self) -> Option<&Error> {
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match *self {
-            ResponseError::InvalidData(ref error) => Some(error),
+            Error::Io(ref error) => Some(error),
             _ => None,
         }
     }
 }
 
-impl convert::From<io::Error> for ResponseError {
+impl convert::From<io::Error> for Error {
     fn from(error: io::Error) -> Self {
-        ResponseError::InvalidData(error)
+        Error::Io(error)
     }
 }
 
-impl fmt::Debug for ResponseError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("ResponseError")
-            .field("kind", &self)
-            .finish()
+/// Conversion is intended for non-`Ok` statuses only,
+/// see [Status::into_result](enum.Status.html#method.into_result) for a checked one.
+impl convert::From<Status> for Error {
+    fn from(status: Status) -> Self {
+        Error::Status(status)
     }
 }
 
-impl fmt::Display for ResponseError {
+impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str(self.description())
+        match *self {
+            Error::UnknownMagic(magic) => write!(f, "unknown magic byte 0x{:02x}", magic),
+            Error::UnexpectedMagic(magic) => write!(f, "unexpected {:?} magic byte", magic),
+            Error::UnknownDataType(data_type) => write!(f, "unknown data type 0x{:02x}", data_type),
+            Error::UnknownVBucketState(state) => write!(f, "unknown vBucket state 0x{:08x}", state),
+            Error::InvalidBodyLength(length) =>
+                write!(f, "body length {} is less than extras and key lengths", length),
+            Error::InvalidExtras(command) => write!(f, "extras can't be used with {:?} command", command),
+            Error::UnexpectedCommand(command) => write!(f, "unexpected {:?} command", command),
+            Error::InvalidLength { expected, actual } =>
+                write!(f, "expected {} bytes, got {} bytes", expected, actual),
+            Error::MissingKey(command) => write!(f, "{:?} command requires a key", command),
+            Error::UnexpectedValue(command) => write!(f, "{:?} command does not allow a value", command),
+            Error::KeyTooLong(length) => write!(f, "key length {} exceeds the limit", length),
+            Error::ExtrasTooLong(length) => write!(f, "extras length {} exceeds the limit", length),
+            Error::BodyTooLong(length) => write!(f, "body length {} does not fit into u32", length),
+            Error::BodyLimitExceeded { length, limit } =>
+                write!(f, "body length {} exceeds the limit of {} bytes", length, limit),
+            Error::NotEnoughCapacity { required, remaining } =>
+                write!(f, "packet requires {} bytes, but only {} bytes are available", required, remaining),
+            Error::InvalidExpiration(expiry) => write!(f, "invalid expiration {:?}", expiry),
+            Error::Status(status) => write!(f, "server responded with error: {}", status),
+            Error::Io(ref error) => write!(f, "I/O error: {}", error),
+        }
     }
 }
@@ -0,0 +1,136 @@
+use std::fmt;
+use std::default::Default;
+
+use bytes::{Buf, BufMut};
+use byteorder::NetworkEndian;
+
+use super::{Extras, check_length, Expiration, Expiry, Clock};
+use ::{Command, Error};
+
+/// Extras container for `Flush` requests.
+///
+/// Expiration is optional, flush is executed immediately if it is missing
+/// and extras are encoded as a zero-length section in that case.
+///
+/// Since `FlushQ` requests use the same format, [FlushQ type alias](type.FlushQ.html)
+/// can be used in order to provide consistent interface.
+///
+/// # Examples
+///
+/// ```rust
+/// use memcache_proto::extras::Flush;
+///
+/// let mut extras = Flush::default();
+/// extras.set_expiration(Some(60_u32));
+/// ```
+///
+/// With builder interface:
+///
+/// ```rust
+/// use memcache_proto::extras::Flush;
+///
+/// let extras = Flush::build()
+///     .expiration(60_u32)
+///     .finish();
+/// ```
+pub struct Flush {
+    expiration: Option<Expiry>,
+}
+
+/// Extras container for `FlushQ` requests.
+///
+/// It is an alias for [Flush](struct.Flush.html) struct,
+/// see [the module documentation](struct.Flush.html) for more.
+pub type FlushQ = Flush;
+
+impl Flush {
+
+    pub fn new<T: Expiration>(expiration: Option<T>) -> Self {
+        Self {
+            expiration: expiration.map(|value| value.as_expiry()),
+        }
+    }
+
+    pub fn build() -> FlushBuilder {
+        FlushBuilder(Flush::default())
+    }
+
+    pub fn set_expiration<T: Expiration>(&mut self, value: Option<T>) {
+        self.expiration = value.map(|value| value.as_expiry());
+    }
+
+    pub fn expiration(&self) -> Option<Expiry> {
+        self.expiration
+    }
+}
+
+pub struct FlushBuilder(Flush);
+
+impl FlushBuilder {
+
+    pub fn expiration<T: Expiration>(mut self, expiration: T) -> Self {
+        self.0.set_expiration(Some(expiration));
+        self
+    }
+
+    pub fn finish(self) -> Flush {
+        self.0
+    }
+
+}
+
+impl Extras for Flush {
+    // Expiration is optional, so empty extras are valid as well
+    const LENGTH: usize = 4;
+
+    fn request_commands() -> &'static [Command] {
+        &[Command::Flush, Command::FlushQ]
+    }
+
+    fn response_commands() -> &'static [Command] {
+        &[]
+    }
+
+    fn read<T: Buf>(buf: &mut T) -> Result<Self, Error> {
+        // Expiration is optional, so empty extras are accepted as well
+        let expiration = if buf.has_remaining() {
+            check_length(buf, Self::LENGTH)?;
+            Some(Expiry::from_seconds(buf.get_u32::<NetworkEndian>()))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            expiration: expiration,
+        })
+    }
+
+    fn write_with<T: BufMut, C: Clock>(&self, buf: &mut T, clock: &C) -> Result<(), Error> {
+        if let Some(expiration) = self.expiration {
+            let expiration = expiration.to_seconds(clock)?;
+            buf.put_u32::<NetworkEndian>(expiration);
+        }
+        Ok(())
+    }
+
+    fn expiry(&self) -> Option<Expiry> {
+        self.expiration
+    }
+
+}
+
+impl Default for Flush {
+    fn default() -> Self {
+        Self {
+            expiration: None,
+        }
+    }
+}
+
+impl fmt::Debug for Flush {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Flush")
+            .field("expiration", &self.expiration())
+            .finish()
+    }
+}
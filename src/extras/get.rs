@@ -1,11 +1,11 @@
-use std::io;
 use std::default::Default;
 use std::fmt;
 
 use bytes::{Buf, BufMut};
 use byteorder::NetworkEndian;
 
-use super::Extras;
+use super::{Extras, Clock, check_length};
+use ::{Command, Error};
 
 /// Extras container for `Get` responses.
 ///
@@ -89,13 +89,28 @@ impl GetBuilder {
 }
 
 impl Extras for Get {
-    fn read<T: Buf>(buf: &mut T) -> io::Result<Self> {
+    const LENGTH: usize = 4;
+
+    fn request_commands() -> &'static [Command] {
+        // `Get` requests must not have extras, flags are sent only in responses
+        &[]
+    }
+
+    fn response_commands() -> &'static [Command] {
+        &[
+            Command::Get, Command::GetQ, Command::GetK, Command::GetKQ,
+            Command::Touch, Command::Gat, Command::GatQ, Command::RGet,
+        ]
+    }
+
+    fn read<T: Buf>(buf: &mut T) -> Result<Self, Error> {
+        check_length(buf, Self::LENGTH)?;
         Ok(Self {
             flags: buf.get_u32::<NetworkEndian>(),
         })
     }
 
-    fn write<T: BufMut>(&self, buf: &mut T) -> io::Result<()> {
+    fn write_with<T: BufMut, C: Clock>(&self, buf: &mut T, _: &C) -> Result<(), Error> {
         buf.put_u32::<NetworkEndian>(self.flags);
         Ok(())
     }
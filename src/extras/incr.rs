@@ -1,11 +1,11 @@
-use std::io;
 use std::fmt;
 use std::default::Default;
 
 use bytes::{Buf, BufMut};
 use byteorder::NetworkEndian;
 
-use super::{Extras, Expiration};
+use super::{Extras, check_length, Expiration, Expiry, Clock};
+use ::{Command, Error};
 
 /// Extras container for `Increment` requests.
 ///
@@ -36,7 +36,7 @@ use super::{Extras, Expiration};
 pub struct Increment{
     amount: u64,
     initial: u64,
-    expiration: u32,
+    expiration: Expiry,
 }
 
 /// Extras container for `Decrement` requests.
@@ -51,7 +51,7 @@ impl Increment {
         Self {
             amount: amount,
             initial: initial,
-            expiration: expiration.get_timeout(),
+            expiration: expiration.as_expiry(),
         }
     }
 
@@ -76,10 +76,10 @@ impl Increment {
     }
 
     pub fn set_expiration<T: Expiration>(&mut self, value: T) {
-        self.expiration = value.get_timeout();
+        self.expiration = value.as_expiry();
     }
 
-    pub fn expiration(&self) -> u32 {
+    pub fn expiration(&self) -> Expiry {
         self.expiration
     }
 
@@ -109,21 +109,38 @@ impl IncrementBuilder {
 }
 
 impl Extras for Increment {
-    fn read<T: Buf>(buf: &mut T) -> io::Result<Self> {
+    const LENGTH: usize = 20;
+
+    fn request_commands() -> &'static [Command] {
+        &[Command::Increment, Command::IncrementQ, Command::Decrement, Command::DecrementQ]
+    }
+
+    fn response_commands() -> &'static [Command] {
+        // Counter value is sent in the response body instead
+        &[]
+    }
+
+    fn read<T: Buf>(buf: &mut T) -> Result<Self, Error> {
+        check_length(buf, Self::LENGTH)?;
         Ok(Self{
             amount: buf.get_u64::<NetworkEndian>(),
             initial: buf.get_u64::<NetworkEndian>(),
-            expiration: buf.get_u32::<NetworkEndian>(),
+            expiration: Expiry::from_seconds(buf.get_u32::<NetworkEndian>()),
         })
     }
 
-    fn write<T: BufMut>(&self, buf: &mut T) -> io::Result<()> {
+    fn write_with<T: BufMut, C: Clock>(&self, buf: &mut T, clock: &C) -> Result<(), Error> {
+        let expiration = self.expiration.to_seconds(clock)?;
         buf.put_u64::<NetworkEndian>(self.amount);
         buf.put_u64::<NetworkEndian>(self.initial);
-        buf.put_u32::<NetworkEndian>(self.expiration);
+        buf.put_u32::<NetworkEndian>(expiration);
         Ok(())
     }
 
+    fn expiry(&self) -> Option<Expiry> {
+        Some(self.expiration)
+    }
+
 }
 
 impl Default for Increment {
@@ -131,7 +148,7 @@ impl Default for Increment {
         Self {
             amount: 0,
             initial: 0,
-            expiration: 0,
+            expiration: Expiry::Never,
         }
     }
 }
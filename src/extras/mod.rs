@@ -1,19 +1,80 @@
-use std::io;
 use bytes::{Buf, BufMut};
 
+use super::{Command, Error};
+
 mod get;
 mod set;
 mod incr;
+mod touch;
+mod flush;
+mod verbosity;
+mod vbucket;
+mod tap;
+mod range;
 mod traits;
 
 pub use self::get::{Get, GetK, GetQ, GetKQ};
 pub use self::set::{Set, Add, Replace};
 pub use self::incr::{Increment, Decrement};
-pub use self::traits::Expiration;
+pub use self::touch::{Touch, Gat, GatQ};
+pub use self::flush::{Flush, FlushQ};
+pub use self::verbosity::Verbosity;
+pub use self::vbucket::SetVBucket;
+pub use self::tap::{TapConnect, TapFlags};
+pub use self::range::Range;
+pub use self::traits::{Expiration, Expiry, Clock, SystemClock};
 
+/// Serializable extras section of the protocol packet.
 pub trait Extras: Sized {
-    fn read<T: Buf>(buf: &mut T) -> io::Result<Self>;
-    fn write<T: BufMut>(&self, buf: &mut T) -> io::Result<()>;
+    /// Maximum length of the serialized extras.
+    ///
+    /// Most extras are always exactly `LENGTH` bytes long, ones with the optional fields,
+    /// ex. [Flush](struct.Flush.html) without expiration, might be shorter.
+    const LENGTH: usize;
+
+    /// Request commands, which are allowed to carry this extras.
+    fn request_commands() -> &'static [Command];
+
+    /// Response commands, which are carrying this extras.
+    fn response_commands() -> &'static [Command];
+
+    /// Deserialize extras from the whole `buf`.
+    ///
+    /// Fails with `Error::InvalidLength` if `buf` is longer than [LENGTH](#associatedconstant.LENGTH)
+    /// bytes or does not match any of the supported lengths.
+    fn read<T: Buf>(buf: &mut T) -> Result<Self, Error>;
+
+    /// Serialize extras, relative expirations longer than 30 days are converted
+    /// into unix timestamps with the [SystemClock](struct.SystemClock.html).
+    fn write<T: BufMut>(&self, buf: &mut T) -> Result<(), Error> {
+        self.write_with(buf, &SystemClock)
+    }
+
+    /// Serialize extras, relative expirations are converted with a custom `clock`.
+    ///
+    /// Extras without expiration are ignoring the `clock`.
+    fn write_with<T: BufMut, C: Clock>(&self, buf: &mut T, clock: &C) -> Result<(), Error>;
+
+    /// Expiration, which is serialized as the last 4 bytes of the extras.
+    ///
+    /// It is kept by the [Request](../struct.Request.html) in order to convert
+    /// relative expirations when the request is encoded, not when extras are provided.
+    fn expiry(&self) -> Option<Expiry> {
+        None
+    }
+}
+
+// Extras are read from the untrusted input, so all the `Buf` getters
+// must be preceded by this check in order to not panic.
+fn check_length<T: Buf>(buf: &T, expected: usize) -> Result<(), Error> {
+    if buf.remaining() != expected {
+        return Err(Error::InvalidLength {
+            expected: expected,
+            actual: buf.remaining(),
+        });
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -0,0 +1,161 @@
+use std::fmt;
+use std::default::Default;
+
+use bytes::{Buf, BufMut};
+use byteorder::NetworkEndian;
+
+use super::{Extras, Clock, check_length};
+use ::{Command, Error};
+
+/// Extras container for the range requests: `RGet`, `RSet`, `RAppend`, `RPrepend`,
+/// `RDelete`, `RIncr`, `RDecr` and their quiet versions.
+///
+/// Range start key is sent as the request key and the end key is placed
+/// at the beginning of the request value, so end key length is a part of extras.
+/// Request constructors, like [Request::rget](../struct.Request.html#method.rget),
+/// are filling it automatically.
+///
+/// # Examples
+///
+/// ```rust
+/// use memcache_proto::extras::Range;
+///
+/// let mut extras = Range::default();
+/// extras.set_max_results(100);
+/// ```
+///
+/// With builder interface:
+///
+/// ```rust
+/// use memcache_proto::extras::Range;
+///
+/// let extras = Range::build()
+///     .max_results(100)
+///     .flags(0x01)
+///     .finish();
+/// ```
+pub struct Range {
+    end_key_length: u16,
+    flags: u8,
+    max_results: u32,
+}
+
+impl Range {
+
+    pub fn new(max_results: u32) -> Self {
+        Self {
+            end_key_length: 0,
+            flags: 0,
+            max_results: max_results,
+        }
+    }
+
+    pub fn build() -> RangeBuilder {
+        RangeBuilder(Range::default())
+    }
+
+    pub fn set_end_key_length(&mut self, value: u16) {
+        self.end_key_length = value;
+    }
+
+    pub fn end_key_length(&self) -> u16 {
+        self.end_key_length
+    }
+
+    pub fn set_flags(&mut self, value: u8) {
+        self.flags = value;
+    }
+
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// Set maximum number of items to process, `0` means no limit.
+    pub fn set_max_results(&mut self, value: u32) {
+        self.max_results = value;
+    }
+
+    pub fn max_results(&self) -> u32 {
+        self.max_results
+    }
+}
+
+pub struct RangeBuilder(Range);
+
+impl RangeBuilder {
+
+    pub fn end_key_length(mut self, end_key_length: u16) -> Self {
+        self.0.set_end_key_length(end_key_length);
+        self
+    }
+
+    pub fn flags(mut self, flags: u8) -> Self {
+        self.0.set_flags(flags);
+        self
+    }
+
+    pub fn max_results(mut self, max_results: u32) -> Self {
+        self.0.set_max_results(max_results);
+        self
+    }
+
+    pub fn finish(self) -> Range {
+        self.0
+    }
+
+}
+
+impl Extras for Range {
+    const LENGTH: usize = 8;
+
+    fn request_commands() -> &'static [Command] {
+        &[
+            Command::RGet, Command::RSet, Command::RSetQ, Command::RAppend, Command::RAppendQ,
+            Command::RPrepend, Command::RPrependQ, Command::RDelete, Command::RDeleteQ,
+            Command::RIncr, Command::RIncrQ, Command::RDecr, Command::RDecrQ,
+        ]
+    }
+
+    fn response_commands() -> &'static [Command] {
+        &[]
+    }
+
+    fn read<T: Buf>(buf: &mut T) -> Result<Self, Error> {
+        check_length(buf, Self::LENGTH)?;
+        let end_key_length = buf.get_u16::<NetworkEndian>();
+        // Reserved byte
+        buf.get_u8();
+        let flags = buf.get_u8();
+
+        Ok(Self {
+            end_key_length: end_key_length,
+            flags: flags,
+            max_results: buf.get_u32::<NetworkEndian>(),
+        })
+    }
+
+    fn write_with<T: BufMut, C: Clock>(&self, buf: &mut T, _: &C) -> Result<(), Error> {
+        buf.put_u16::<NetworkEndian>(self.end_key_length);
+        buf.put_u8(0);
+        buf.put_u8(self.flags);
+        buf.put_u32::<NetworkEndian>(self.max_results);
+        Ok(())
+    }
+
+}
+
+impl Default for Range {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl fmt::Debug for Range {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Range")
+            .field("end_key_length", &self.end_key_length())
+            .field("flags", &self.flags())
+            .field("max_results", &self.max_results())
+            .finish()
+    }
+}
@@ -1,4 +1,3 @@
-use std::io;
 use std::fmt;
 use std::default::Default;
 use std::convert::Into;
@@ -6,7 +5,8 @@ use std::convert::Into;
 use bytes::{Buf, BufMut};
 use byteorder::NetworkEndian;
 
-use super::{Extras, Expiration};
+use super::{Extras, check_length, Expiration, Expiry, Clock};
+use ::{Command, Error};
 
 /// Extras container for `Set` requests.
 ///
@@ -37,7 +37,7 @@ use super::{Extras, Expiration};
 /// ```
 pub struct Set{
     flags: u32,
-    expiration: u32,
+    expiration: Expiry,
 }
 
 /// Extras container for `Add` requests.
@@ -57,7 +57,7 @@ impl Set {
     pub fn new<T: Expiration>(flags: u32, expiration: T) -> Set {
         Self {
             flags: flags,
-            expiration: expiration.get_timeout(),
+            expiration: expiration.as_expiry(),
         }
     }
 
@@ -74,10 +74,10 @@ impl Set {
     }
 
     pub fn set_expiration<T: Expiration>(&mut self, value: T) {
-        self.expiration = value.get_timeout();
+        self.expiration = value.as_expiry();
     }
 
-    pub fn expiration(&self) -> u32 {
+    pub fn expiration(&self) -> Expiry {
         self.expiration
     }
 }
@@ -103,26 +103,42 @@ impl SetBuilder {
 }
 
 impl Extras for Set {
-    fn read<T: Buf>(buf: &mut T) -> io::Result<Self> {
+    const LENGTH: usize = 8;
+
+    fn request_commands() -> &'static [Command] {
+        &[Command::Set, Command::SetQ, Command::Add, Command::AddQ, Command::Replace, Command::ReplaceQ]
+    }
+
+    fn response_commands() -> &'static [Command] {
+        &[]
+    }
+
+    fn read<T: Buf>(buf: &mut T) -> Result<Self, Error> {
+        check_length(buf, Self::LENGTH)?;
         Ok(Self{
             flags: buf.get_u32::<NetworkEndian>(),
-            expiration: buf.get_u32::<NetworkEndian>(),
+            expiration: Expiry::from_seconds(buf.get_u32::<NetworkEndian>()),
         })
     }
 
-    fn write<T: BufMut>(&self, buf: &mut T) -> io::Result<()> {
+    fn write_with<T: BufMut, C: Clock>(&self, buf: &mut T, clock: &C) -> Result<(), Error> {
+        let expiration = self.expiration.to_seconds(clock)?;
         buf.put_u32::<NetworkEndian>(self.flags);
-        buf.put_u32::<NetworkEndian>(self.expiration);
+        buf.put_u32::<NetworkEndian>(expiration);
         Ok(())
     }
 
+    fn expiry(&self) -> Option<Expiry> {
+        Some(self.expiration)
+    }
+
 }
 
 impl Default for Set {
     fn default() -> Self {
         Self {
             flags: 0,
-            expiration: 0,
+            expiration: Expiry::Never,
         }
     }
 }
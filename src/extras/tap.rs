@@ -0,0 +1,327 @@
+use std::fmt;
+use std::ops;
+use std::default::Default;
+
+use bytes::{Buf, BufMut};
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{Extras, Clock, check_length};
+use ::{Command, Error};
+
+/// Flags of the `TapConnect` request.
+///
+/// Reference: https://github.com/memcached/memcached/wiki/BinaryProtocolRevamped#tap-connect
+///
+/// # Examples
+///
+/// ```rust
+/// use memcache_proto::extras::TapFlags;
+///
+/// let flags = TapFlags::DUMP | TapFlags::KEYS_ONLY;
+/// assert!(flags.contains(TapFlags::DUMP));
+/// assert!(!flags.contains(TapFlags::TAKEOVER));
+/// assert_eq!(flags.bits(), 0x22);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct TapFlags(u32);
+
+impl TapFlags {
+    /// Send items changed since the backfill date, which is sent in the request body.
+    pub const BACKFILL: TapFlags = TapFlags(0x01);
+    /// Dump existing items and close the stream.
+    pub const DUMP: TapFlags = TapFlags(0x02);
+    /// Stream only the vBuckets listed in the request body.
+    pub const LIST_VBUCKETS: TapFlags = TapFlags(0x04);
+    /// Take over the listed vBuckets from the server.
+    pub const TAKEOVER: TapFlags = TapFlags(0x08);
+    /// Client is able to acknowledge TAP messages.
+    pub const SUPPORT_ACK: TapFlags = TapFlags(0x10);
+    /// Send keys only, without values.
+    pub const KEYS_ONLY: TapFlags = TapFlags(0x20);
+    /// Send checkpoint messages.
+    pub const CHECKPOINT: TapFlags = TapFlags(0x40);
+    /// Client is registered and its stream should survive reconnects.
+    pub const REGISTERED_CLIENT: TapFlags = TapFlags(0x80);
+
+    pub fn empty() -> TapFlags {
+        TapFlags(0)
+    }
+
+    /// Create flags from the raw protocol value, unknown bits are preserved.
+    pub fn from_bits(bits: u32) -> TapFlags {
+        TapFlags(bits)
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, other: TapFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: TapFlags) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: TapFlags) {
+        self.0 &= !other.0;
+    }
+}
+
+impl ops::BitOr for TapFlags {
+    type Output = TapFlags;
+
+    fn bitor(self, other: TapFlags) -> TapFlags {
+        TapFlags(self.0 | other.0)
+    }
+}
+
+impl ops::BitOrAssign for TapFlags {
+    fn bitor_assign(&mut self, other: TapFlags) {
+        self.insert(other);
+    }
+}
+
+/// Extras container for `TapConnect` requests.
+///
+/// Besides the flags, which are sent in the extras section, it also holds
+/// an optional request body: backfill date and the vBuckets list.
+/// Setting one of them also sets the corresponding flag, while `BACKFILL` and `LIST_VBUCKETS`
+/// flags without the matching field are rejected when extras are serialized.
+///
+/// # Examples
+///
+/// ```rust
+/// use memcache_proto::extras::{TapConnect, TapFlags};
+///
+/// let mut extras = TapConnect::default();
+/// extras.set_flags(TapFlags::DUMP);
+/// ```
+///
+/// With builder interface:
+///
+/// ```rust
+/// use memcache_proto::extras::{TapConnect, TapFlags};
+///
+/// let extras = TapConnect::build()
+///     .flags(TapFlags::SUPPORT_ACK)
+///     .backfill(0)
+///     .vbuckets(vec![1, 2, 3])
+///     .finish();
+///
+/// assert!(extras.flags().contains(TapFlags::BACKFILL | TapFlags::LIST_VBUCKETS));
+/// ```
+pub struct TapConnect {
+    flags: TapFlags,
+    backfill: Option<u64>,
+    vbuckets: Option<Vec<u16>>,
+}
+
+impl TapConnect {
+
+    pub fn new(flags: TapFlags) -> Self {
+        Self {
+            flags: flags,
+            backfill: None,
+            vbuckets: None,
+        }
+    }
+
+    pub fn build() -> TapConnectBuilder {
+        TapConnectBuilder(TapConnect::default())
+    }
+
+    pub fn set_flags(&mut self, value: TapFlags) {
+        self.flags = value;
+    }
+
+    pub fn flags(&self) -> TapFlags {
+        self.flags
+    }
+
+    pub fn set_backfill(&mut self, value: Option<u64>) {
+        match value {
+            Some(..) => self.flags.insert(TapFlags::BACKFILL),
+            None => self.flags.remove(TapFlags::BACKFILL),
+        }
+        self.backfill = value;
+    }
+
+    /// Backfill date, only items changed since this time will be sent.
+    pub fn backfill(&self) -> Option<u64> {
+        self.backfill
+    }
+
+    pub fn set_vbuckets(&mut self, value: Option<Vec<u16>>) {
+        match value {
+            Some(..) => self.flags.insert(TapFlags::LIST_VBUCKETS),
+            None => self.flags.remove(TapFlags::LIST_VBUCKETS),
+        }
+        self.vbuckets = value;
+    }
+
+    pub fn vbuckets(&self) -> Option<&[u16]> {
+        self.vbuckets.as_ref().map(|vbuckets| vbuckets.as_slice())
+    }
+
+    /// Serialize request body: backfill date followed by the vBuckets list.
+    ///
+    /// Empty body is returned if neither of them is set.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the vBuckets list is longer than `u16::MAX`
+    /// or if `BACKFILL` and `LIST_VBUCKETS` flags do not match the set fields.
+    pub fn body(&self) -> Result<Vec<u8>, Error> {
+        self.check_flags()?;
+        let mut buf = Vec::new();
+        if let Some(backfill) = self.backfill {
+            buf.put_u64::<NetworkEndian>(backfill);
+        }
+        if let Some(ref vbuckets) = self.vbuckets {
+            if vbuckets.len() > u16::max_value() as usize {
+                return Err(Error::InvalidLength {
+                    expected: u16::max_value() as usize,
+                    actual: vbuckets.len(),
+                });
+            }
+            buf.put_u16::<NetworkEndian>(vbuckets.len() as u16);
+            for vbucket in vbuckets {
+                buf.put_u16::<NetworkEndian>(*vbucket);
+            }
+        }
+
+        Ok(buf)
+    }
+
+    /// Parse request body, which presence is defined by the already read flags.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the body length does not match the flags.
+    pub fn read_body(&mut self, body: &[u8]) -> Result<(), Error> {
+        let mut offset = 0;
+        let mut backfill = None;
+        let mut vbuckets = None;
+
+        if self.flags.contains(TapFlags::BACKFILL) {
+            Self::check_body_length(body, offset + 8)?;
+            backfill = Some(NetworkEndian::read_u64(&body[offset..]));
+            offset += 8;
+        }
+        if self.flags.contains(TapFlags::LIST_VBUCKETS) {
+            Self::check_body_length(body, offset + 2)?;
+            let count = NetworkEndian::read_u16(&body[offset..]) as usize;
+            offset += 2;
+            Self::check_body_length(body, offset + count * 2)?;
+            vbuckets = Some(body[offset..offset + count * 2].chunks(2)
+                .map(NetworkEndian::read_u16)
+                .collect());
+            offset += count * 2;
+        }
+        if offset != body.len() {
+            return Err(Error::InvalidLength {
+                expected: offset,
+                actual: body.len(),
+            });
+        }
+
+        self.backfill = backfill;
+        self.vbuckets = vbuckets;
+        Ok(())
+    }
+
+    // Flags, which are set via `set_flags` without the corresponding fields,
+    // would announce a body which is never sent
+    fn check_flags(&self) -> Result<(), Error> {
+        if self.flags.contains(TapFlags::BACKFILL) != self.backfill.is_some()
+                || self.flags.contains(TapFlags::LIST_VBUCKETS) != self.vbuckets.is_some() {
+            return Err(Error::InvalidExtras(Command::TapConnect));
+        }
+
+        Ok(())
+    }
+
+    fn check_body_length(body: &[u8], expected: usize) -> Result<(), Error> {
+        if body.len() < expected {
+            return Err(Error::InvalidLength {
+                expected: expected,
+                actual: body.len(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+pub struct TapConnectBuilder(TapConnect);
+
+impl TapConnectBuilder {
+
+    /// Add `flags` to the already set ones.
+    pub fn flags(mut self, flags: TapFlags) -> Self {
+        self.0.flags.insert(flags);
+        self
+    }
+
+    pub fn backfill(mut self, backfill: u64) -> Self {
+        self.0.set_backfill(Some(backfill));
+        self
+    }
+
+    pub fn vbuckets(mut self, vbuckets: Vec<u16>) -> Self {
+        self.0.set_vbuckets(Some(vbuckets));
+        self
+    }
+
+    pub fn finish(self) -> TapConnect {
+        self.0
+    }
+
+}
+
+impl Extras for TapConnect {
+    const LENGTH: usize = 4;
+
+    fn request_commands() -> &'static [Command] {
+        &[Command::TapConnect]
+    }
+
+    fn response_commands() -> &'static [Command] {
+        &[]
+    }
+
+    /// Read flags only, see [TapConnect::read_body](#method.read_body) for the rest.
+    fn read<T: Buf>(buf: &mut T) -> Result<Self, Error> {
+        check_length(buf, Self::LENGTH)?;
+        Ok(Self::new(TapFlags::from_bits(buf.get_u32::<NetworkEndian>())))
+    }
+
+    fn write_with<T: BufMut, C: Clock>(&self, buf: &mut T, _: &C) -> Result<(), Error> {
+        self.check_flags()?;
+        buf.put_u32::<NetworkEndian>(self.flags.bits());
+        Ok(())
+    }
+
+}
+
+impl Default for TapConnect {
+    fn default() -> Self {
+        Self::new(TapFlags::empty())
+    }
+}
+
+impl fmt::Debug for TapConnect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TapConnect")
+            .field("flags", &self.flags())
+            .field("backfill", &self.backfill())
+            .field("vbuckets", &self.vbuckets())
+            .finish()
+    }
+}
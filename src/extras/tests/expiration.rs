@@ -1,52 +1,181 @@
 use std::time::{SystemTime, Duration, UNIX_EPOCH};
+use std::u32;
 
-use extras::Expiration;
+use ::{Request, Command, Error};
+use extras::{Extras, Expiration, Expiry, Clock, Touch, Set, Flush};
 use extras::traits::MAX_SECONDS;
 
+// 2017-07-14 02:40:00 UTC
+const NOW: u64 = 1_500_000_000;
+
+struct FixedClock(SystemTime);
+
+impl FixedClock {
+    fn new() -> FixedClock {
+        FixedClock(UNIX_EPOCH + Duration::from_secs(NOW))
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
 #[test]
 fn test_conversion_small() {
-    assert_eq!(3600u32, u32::get_timeout(&3600));
+    assert_eq!(3600, 3600_u32.get_timeout_with(&FixedClock::new()).unwrap());
 }
 
 #[test]
 fn test_conversion_equal_to_limit() {
-    let limit: u32 = MAX_SECONDS;
-    assert_eq!(limit, u32::get_timeout(&limit));
+    assert_eq!(MAX_SECONDS, MAX_SECONDS.get_timeout_with(&FixedClock::new()).unwrap());
 }
 
 #[test]
 fn test_conversion_into_timestamp() {
-    let value = MAX_SECONDS + 1;
-    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
-    let expected = now.checked_add(Duration::new(value as u64, 0)).unwrap().as_secs() as u32;
-    assert_eq!(expected, u32::get_timeout(&value));
+    let value = MAX_SECONDS + 1024;
+    let expected = NOW as u32 + value;
+    assert_eq!(expected, value.get_timeout_with(&FixedClock::new()).unwrap());
 }
 
 #[test]
-fn test_simple_timeout() {
-    let timeout: u32 = 3600;
+fn test_zero_is_never() {
+    assert_eq!(Expiry::Never, 0_u32.as_expiry());
+    assert_eq!(0, 0_u32.get_timeout_with(&FixedClock::new()).unwrap());
+}
 
-    assert_eq!(3600u32, timeout.get_timeout());
+#[test]
+fn test_duration() {
+    let timeout = Duration::new(3600, 0);
+    assert_eq!(3600, timeout.get_timeout_with(&FixedClock::new()).unwrap());
 }
 
 #[test]
-fn test_more_that_30_days_timeout() {
-    // Since this timeout is bigger than "30 days in seconds",
-    // conversion should return current timestamp + 1024 seconds.
-    let timeout: u32 = 60 * 60 * 24 * 30 + 1024;
+fn test_sub_second_duration_is_rounded_up() {
+    let timeout = Duration::from_millis(1500);
+    assert_eq!(2, timeout.get_timeout_with(&FixedClock::new()).unwrap());
 
-    let now = SystemTime::now();
-    let ts = now.duration_since(UNIX_EPOCH).unwrap()
-        .checked_add(Duration::new(timeout as u64, 0)).unwrap().as_secs() as u32;
-    let difference = ts - timeout.get_timeout();
-    // Stupid way to check that conversion into u32 returned
-    // value almost equal to expected timestamp
-    assert!(difference == 0 || difference == 1);
+    let timeout = Duration::from_millis(1);
+    assert_eq!(1, timeout.get_timeout_with(&FixedClock::new()).unwrap());
 }
 
 #[test]
-fn test_small_duration() {
-    let timeout = Duration::new(3600, 0);
+fn test_optional_duration() {
+    let clock = FixedClock::new();
+    assert_eq!(0, None::<Duration>.get_timeout_with(&clock).unwrap());
+    assert_eq!(60, Some(Duration::from_secs(60)).get_timeout_with(&clock).unwrap());
+}
+
+#[test]
+fn test_system_time() {
+    let at = UNIX_EPOCH + Duration::from_secs(NOW + 60);
+    assert_eq!(Expiry::At(at), at.as_expiry());
+    assert_eq!(NOW as u32 + 60, at.get_timeout_with(&FixedClock::new()).unwrap());
+}
+
+#[test]
+fn test_from_seconds() {
+    assert_eq!(Expiry::Never, Expiry::from_seconds(0));
+    assert_eq!(Expiry::Relative(Duration::from_secs(60)), Expiry::from_seconds(60));
+    assert_eq!(Expiry::At(UNIX_EPOCH + Duration::from_secs(NOW)), Expiry::from_seconds(NOW as u32));
+}
+
+#[test]
+fn test_timestamp_after_2106() {
+    let at = UNIX_EPOCH + Duration::from_secs(u32::max_value() as u64 + 1);
+    match at.get_timeout_with(&FixedClock::new()) {
+        Err(Error::InvalidExpiration(Expiry::At(value))) => assert_eq!(value, at),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn test_relative_after_2106() {
+    let timeout = Duration::from_secs(u32::max_value() as u64);
+    match timeout.get_timeout_with(&FixedClock::new()) {
+        Err(Error::InvalidExpiration(Expiry::Relative(value))) => assert_eq!(value, timeout),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn test_zero_relative() {
+    // Zero would be sent as "never expire"
+    let clock = FixedClock::new();
+    assert!(Duration::from_secs(0).get_timeout_with(&clock).is_err());
+    assert!(Some(Duration::from_secs(0)).get_timeout_with(&clock).is_err());
+    assert_eq!(0, 0_u32.get_timeout_with(&clock).unwrap());
+    assert_eq!(1, Duration::from_millis(1).get_timeout_with(&clock).unwrap());
+}
+
+#[test]
+fn test_timestamp_before_epoch() {
+    let at = UNIX_EPOCH - Duration::from_secs(60);
+    assert!(at.get_timeout_with(&FixedClock::new()).is_err());
+}
+
+#[test]
+fn test_timestamp_within_relative_range() {
+    // Server would treat this value as a relative one
+    let at = UNIX_EPOCH + Duration::from_secs(60);
+    assert!(at.get_timeout_with(&FixedClock::new()).is_err());
+}
+
+#[test]
+fn test_invalid_extras_are_not_written() {
+    let extra = Touch::new(UNIX_EPOCH + Duration::from_secs(60));
+    let mut raw: Vec<u8> = vec![];
+    assert!(extra.write(&mut raw).is_err());
+    assert!(raw.is_empty());
+}
+
+#[test]
+fn test_set_written_with_clock() {
+    let extra = Set::new(0xdeadbeef, Duration::from_secs(MAX_SECONDS as u64 + 1024));
+    let expected = NOW as u32 + MAX_SECONDS + 1024;
+
+    let mut raw: Vec<u8> = vec![];
+    extra.write_with(&mut raw, &FixedClock::new()).unwrap();
+    assert_eq!(&raw[..4], &[0xde, 0xad, 0xbe, 0xef]);
+    assert_eq!(&raw[4..], &[(expected >> 24) as u8, (expected >> 16) as u8, (expected >> 8) as u8, expected as u8]);
+
+    let request = Request::set(b"Hello", b"World", extra).unwrap();
+    let mut packet: Vec<u8> = vec![];
+    request.write_with(&mut packet, &FixedClock::new()).unwrap();
+    assert_eq!(&packet[24..32], &raw[..]);
+
+    let encoded = request.encode_vectored_with(&FixedClock::new()).unwrap();
+    let mut vectored: Vec<u8> = vec![];
+    encoded.write_to(&mut vectored).unwrap();
+    assert_eq!(vectored, packet);
+}
+
+#[test]
+fn test_request_converted_when_written() {
+    let extra = Flush::new(Some(Duration::from_secs(MAX_SECONDS as u64 + 1024)));
+    let request = Request::build(Command::Flush).extras(Some(extra)).unwrap().finish();
+
+    let mut first: Vec<u8> = vec![];
+    request.write_with(&mut first, &FixedClock::new()).unwrap();
+    let mut second: Vec<u8> = vec![];
+    request.write_with(&mut second, &FixedClock(UNIX_EPOCH + Duration::from_secs(NOW + 60))).unwrap();
+
+    let expected = NOW as u32 + MAX_SECONDS + 1024;
+    assert_eq!(&first[24..], &[(expected >> 24) as u8, (expected >> 16) as u8, (expected >> 8) as u8, expected as u8]);
+    let expected = expected + 60;
+    assert_eq!(&second[24..], &[(expected >> 24) as u8, (expected >> 16) as u8, (expected >> 8) as u8, expected as u8]);
+}
+
+#[test]
+fn test_request_conversion_error() {
+    let extra = Touch::new(Duration::from_secs(MAX_SECONDS as u64 + 1024));
+    let request = Request::build(Command::Touch).key(Some("Hello")).extras(Some(extra)).unwrap().finish();
 
-    assert_eq!(3600u32, timeout.get_timeout());
+    let mut packet: Vec<u8> = vec![];
+    match request.write_with(&mut packet, &FixedClock(UNIX_EPOCH + Duration::from_secs(u32::MAX as u64))) {
+        Err(Error::InvalidExpiration(_)) => {},
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert!(packet.is_empty());
 }
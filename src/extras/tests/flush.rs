@@ -0,0 +1,50 @@
+use std::io;
+use std::time::Duration;
+
+use ::{Request, Command};
+use extras::{Extras, Expiry, Flush, FlushQ};
+
+#[test]
+fn test_blank() {
+    let extra = Flush::default();
+    let mut raw: Vec<u8> = vec![];
+    extra.write(&mut raw).unwrap();
+    assert_eq!(None, extra.expiration());
+    assert!(raw.is_empty());
+}
+
+#[test]
+fn test_expiration() {
+    let extra = Flush::build().expiration(360_u32).finish();
+    let mut raw: Vec<u8> = vec![];
+    extra.write(&mut raw).unwrap();
+    assert_eq!(Some(Expiry::Relative(Duration::from_secs(360))), extra.expiration());
+    assert_eq!(&[0x00, 0x00, 0x01, 0x68][..], &raw[..]);
+}
+
+#[test]
+fn test_read() {
+    let extra = FlushQ::read(&mut io::Cursor::new(&[0x00, 0x00, 0x0e, 0x10][..])).unwrap();
+    assert_eq!(Some(Expiry::Relative(Duration::from_secs(3600))), extra.expiration());
+
+    let extra = Flush::read(&mut io::Cursor::new(&[][..])).unwrap();
+    assert_eq!(None, extra.expiration());
+}
+
+#[test]
+fn test_request_without_expiration() {
+    let mut request = Request::new(Command::FlushQ);
+    request.set_extras(Some(Flush::default())).unwrap();
+
+    let mut raw: Vec<u8> = vec![];
+    request.write(&mut raw).unwrap();
+    assert_eq!(raw.len(), 24);
+    assert_eq!(raw[4], 0x00);
+}
+
+#[test]
+fn test_read_invalid_length() {
+    assert_eq!(Flush::LENGTH, 4);
+    assert!(Flush::read(&mut io::Cursor::new(&[0x00, 0x00, 0x0e][..])).is_err());
+    assert!(Flush::read(&mut io::Cursor::new(&[0x00; 8][..])).is_err());
+}
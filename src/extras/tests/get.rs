@@ -1,17 +1,47 @@
-use extras::Get;
+use std::io;
+
+use ::{Command, Error};
+use extras::{Extras, Get, GetKQ};
 
 #[test]
 fn test_blank() {
     let extra = Get::default();
-    let raw: &[u8] = extra.as_ref();
+    let mut raw: Vec<u8> = Vec::with_capacity(4);
+    extra.write(&mut raw).unwrap();
     assert_eq!(0, extra.flags());
-    assert_eq!([0; 4], raw);
+    assert_eq!(&[0; 4][..], &raw[..]);
 }
 
 #[test]
 fn test_flags() {
     let extra = Get::new(0xdeadbeef);
-    let raw: &[u8] = extra.as_ref();
+    let mut raw: Vec<u8> = Vec::with_capacity(4);
+    extra.write(&mut raw).unwrap();
+    assert_eq!(0xdeadbeef, extra.flags());
+    assert_eq!(&[0xde, 0xad, 0xbe, 0xef][..], &raw[..]);
+}
+
+#[test]
+fn test_read() {
+    let extra = GetKQ::read(&mut io::Cursor::new(&[0xde, 0xad, 0xbe, 0xef][..])).unwrap();
     assert_eq!(0xdeadbeef, extra.flags());
-    assert_eq!([0xde, 0xad, 0xbe, 0xef], raw);
+}
+
+#[test]
+fn test_commands() {
+    assert!(Get::request_commands().is_empty());
+    assert!(Get::response_commands().contains(&Command::GetK));
+    assert!(Get::response_commands().contains(&Command::Touch));
+}
+
+#[test]
+fn test_read_invalid_length() {
+    match Get::read(&mut io::Cursor::new(&[0xde, 0xad, 0xbe][..])) {
+        Err(Error::InvalidLength { expected: 4, actual: 3 }) => {},
+        _ => panic!("Short extras should be reported"),
+    }
+    match Get::read(&mut io::Cursor::new(&[0x00; 8][..])) {
+        Err(Error::InvalidLength { expected: 4, actual: 8 }) => {},
+        _ => panic!("Oversized extras should be reported"),
+    }
 }
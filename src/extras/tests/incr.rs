@@ -1,49 +1,80 @@
-use extras::Increment;
+use std::io;
+use std::time::Duration;
+
+use ::{Command, Error};
+use extras::{Extras, Expiry, Increment, Decrement};
 
 #[test]
 fn test_blank() {
     let extra = Increment::default();
-    let raw: &[u8] = extra.as_ref();
+    let mut raw: Vec<u8> = Vec::with_capacity(20);
+    extra.write(&mut raw).unwrap();
     assert_eq!(0, extra.amount());
-    assert_eq!([0; 8], &raw[0..8]);
     assert_eq!(0, extra.initial());
-    assert_eq!([0; 8], &raw[8..16]);
-    assert_eq!(0, extra.expiration());
-    assert_eq!([0; 4], &raw[16..20]);
+    assert_eq!(Expiry::Never, extra.expiration());
+    assert_eq!(&[0; 20][..], &raw[..]);
 }
 
 #[test]
-fn test_flags() {
+fn test_amount() {
     let extra = Increment::build().amount(1).finish();
-    let raw: &[u8] = extra.as_ref();
+    let mut raw: Vec<u8> = Vec::with_capacity(20);
+    extra.write(&mut raw).unwrap();
     assert_eq!(1, extra.amount());
     assert_eq!([0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01], &raw[0..8]);
-    assert_eq!(0, extra.initial());
-    assert_eq!([0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], &raw[8..16]);
-    assert_eq!(0, extra.expiration());
-    assert_eq!([0x00, 0x00, 0x00, 0x00], &raw[16..20]);
+    assert_eq!([0x00; 8], &raw[8..16]);
+    assert_eq!([0x00; 4], &raw[16..20]);
 }
 
 #[test]
 fn test_initial() {
     let extra = Increment::build().initial(42).finish();
-    let raw: &[u8] = extra.as_ref();
-    assert_eq!(0, extra.amount());
-    assert_eq!([0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], &raw[0..8]);
+    let mut raw: Vec<u8> = Vec::with_capacity(20);
+    extra.write(&mut raw).unwrap();
     assert_eq!(42, extra.initial());
+    assert_eq!([0x00; 8], &raw[0..8]);
     assert_eq!([0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x2a], &raw[8..16]);
-    assert_eq!(0, extra.expiration());
-    assert_eq!([0x00, 0x00, 0x00, 0x00], &raw[16..20]);
+    assert_eq!([0x00; 4], &raw[16..20]);
 }
 
 #[test]
 fn test_expiration() {
-    let extra = Increment::build().expiration(360).finish();
-    let raw: &[u8] = extra.as_ref();
-    assert_eq!(0, extra.amount());
-    assert_eq!([0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], &raw[0..8]);
-    assert_eq!(0, extra.initial());
-    assert_eq!([0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], &raw[8..16]);
-    assert_eq!(360, extra.expiration());
+    let extra = Increment::build().expiration(360_u32).finish();
+    let mut raw: Vec<u8> = Vec::with_capacity(20);
+    extra.write(&mut raw).unwrap();
+    assert_eq!(Expiry::Relative(Duration::from_secs(360)), extra.expiration());
+    assert_eq!([0x00; 16], &raw[0..16]);
     assert_eq!([0x00, 0x00, 0x01, 0x68], &raw[16..20]);
 }
+
+#[test]
+fn test_read() {
+    let raw = [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x2a,
+        0x00, 0x00, 0x0e, 0x10,
+    ];
+    let extra = Decrement::read(&mut io::Cursor::new(&raw[..])).unwrap();
+    assert_eq!(1, extra.amount());
+    assert_eq!(42, extra.initial());
+    assert_eq!(Expiry::Relative(Duration::from_secs(3600)), extra.expiration());
+}
+
+#[test]
+fn test_commands() {
+    assert!(Increment::request_commands().contains(&Command::IncrementQ));
+    assert!(Increment::request_commands().contains(&Command::Decrement));
+    assert!(Increment::response_commands().is_empty());
+}
+
+#[test]
+fn test_read_invalid_length() {
+    match Increment::read(&mut io::Cursor::new(&[0x00; 16][..])) {
+        Err(Error::InvalidLength { expected: 20, actual: 16 }) => {},
+        _ => panic!("Short extras should be reported"),
+    }
+    match Increment::read(&mut io::Cursor::new(&[0x00; 24][..])) {
+        Err(Error::InvalidLength { expected: 20, actual: 24 }) => {},
+        _ => panic!("Oversized extras should be reported"),
+    }
+}
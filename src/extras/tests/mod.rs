@@ -1,4 +1,10 @@
-//mod get;
-//mod incr;
-//mod set;
+mod get;
+mod incr;
+mod set;
 mod expiration;
+mod touch;
+mod flush;
+mod verbosity;
+mod vbucket;
+mod tap;
+mod range;
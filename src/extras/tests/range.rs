@@ -0,0 +1,60 @@
+use std::io;
+
+use ::{Request, Command};
+use extras::{Extras, Range};
+
+#[test]
+fn test_write() {
+    let extra = Range::build()
+        .end_key_length(3)
+        .flags(0x01)
+        .max_results(0x0100)
+        .finish();
+    let mut raw: Vec<u8> = vec![];
+    extra.write(&mut raw).unwrap();
+    assert_eq!(&[0x00, 0x03, 0x00, 0x01, 0x00, 0x00, 0x01, 0x00][..], &raw[..]);
+}
+
+#[test]
+fn test_read() {
+    let raw = [0x00, 0x05, 0xff, 0x02, 0x00, 0x00, 0x00, 0x0a];
+    let extra = Range::read(&mut io::Cursor::new(&raw[..])).unwrap();
+    assert_eq!(5, extra.end_key_length());
+    assert_eq!(2, extra.flags());
+    assert_eq!(10, extra.max_results());
+}
+
+#[test]
+fn test_request() {
+    let request = Request::rget(b"aaa", b"zz", Range::new(10)).unwrap();
+
+    let mut raw: Vec<u8> = vec![];
+    request.write(&mut raw).unwrap();
+    assert_eq!(raw[1], 0x30);
+    assert_eq!(&raw[2..4], &[0x00, 0x03]);
+    assert_eq!(raw[4], 0x08);
+    assert_eq!(&raw[24..32], &[0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0a]);
+    assert_eq!(&raw[32..35], b"aaa");
+    assert_eq!(&raw[35..], b"zz");
+}
+
+#[test]
+fn test_request_with_value() {
+    let request = Request::rset_q(b"a", b"z", b"value", Range::default()).unwrap();
+
+    assert_eq!(*request.command(), Command::RSetQ);
+    assert_eq!(request.key(), Some(&b"a"[..]));
+    assert_eq!(request.value(), Some(&b"zvalue"[..]));
+    assert_eq!(request.extras().unwrap()[1], 0x01);
+}
+
+#[test]
+fn test_request_errors() {
+    assert!(Request::range::<_, _, &[u8]>(Command::Get, b"a", b"z", None, Range::default()).is_err());
+    assert!(Request::rdelete(b"a", &[0x61; 251][..], Range::default()).is_err());
+}
+
+#[test]
+fn test_read_short() {
+    assert!(Range::read(&mut io::Cursor::new(&[0x00; 7][..])).is_err());
+}
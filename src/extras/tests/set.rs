@@ -1,31 +1,65 @@
-use extras::Set;
+use std::io;
+use std::time::Duration;
+
+use ::{Command, Error};
+use extras::{Extras, Expiry, Set, Replace};
 
 #[test]
 fn test_blank() {
     let extra = Set::default();
     let mut raw: Vec<u8> = Vec::with_capacity(8);
-    extra.write(&mut raw);
+    extra.write(&mut raw).unwrap();
     assert_eq!(0, extra.flags());
-    assert_eq!(0, extra.expiration());
-    assert_eq!(&[0; 8][..], &raw);
+    assert_eq!(Expiry::Never, extra.expiration());
+    assert_eq!(&[0; 8][..], &raw[..]);
 }
 
 #[test]
 fn test_flags() {
     let extra = Set::build().flags(0xdeadbeef).finish();
-    let raw: &[u8] = extra.as_ref();
+    let mut raw: Vec<u8> = Vec::with_capacity(8);
+    extra.write(&mut raw).unwrap();
     assert_eq!(0xdeadbeef, extra.flags());
-    assert_eq!(0, extra.expiration());
+    assert_eq!(Expiry::Never, extra.expiration());
     assert_eq!([0xde, 0xad, 0xbe, 0xef], &raw[0..4]);
     assert_eq!([0x00, 0x00, 0x00, 0x00], &raw[4..8]);
 }
 
 #[test]
 fn test_expiration() {
-    let extra = Set::build().expiration(360).finish();
-    let raw: &[u8] = extra.as_ref();
+    let extra = Set::build().expiration(360_u32).finish();
+    let mut raw: Vec<u8> = Vec::with_capacity(8);
+    extra.write(&mut raw).unwrap();
     assert_eq!(0, extra.flags());
-    assert_eq!(360, extra.expiration());
+    assert_eq!(Expiry::Relative(Duration::from_secs(360)), extra.expiration());
     assert_eq!([0x00, 0x00, 0x00, 0x00], &raw[0..4]);
     assert_eq!([0x00, 0x00, 0x01, 0x68], &raw[4..8]);
 }
+
+#[test]
+fn test_read() {
+    let raw = [0xde, 0xad, 0xbe, 0xef, 0x00, 0x00, 0x0e, 0x10];
+    let extra = Replace::read(&mut io::Cursor::new(&raw[..])).unwrap();
+    assert_eq!(0xdeadbeef, extra.flags());
+    assert_eq!(Expiry::Relative(Duration::from_secs(3600)), extra.expiration());
+}
+
+#[test]
+fn test_commands() {
+    assert!(Set::request_commands().contains(&Command::SetQ));
+    assert!(Set::request_commands().contains(&Command::Add));
+    assert!(!Set::request_commands().contains(&Command::Append));
+    assert!(Set::response_commands().is_empty());
+}
+
+#[test]
+fn test_read_invalid_length() {
+    match Set::read(&mut io::Cursor::new(&[0xde, 0xad, 0xbe, 0xef][..])) {
+        Err(Error::InvalidLength { expected: 8, actual: 4 }) => {},
+        _ => panic!("Short extras should be reported"),
+    }
+    match Set::read(&mut io::Cursor::new(&[0x00; 12][..])) {
+        Err(Error::InvalidLength { expected: 8, actual: 12 }) => {},
+        _ => panic!("Oversized extras should be reported"),
+    }
+}
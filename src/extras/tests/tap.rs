@@ -0,0 +1,83 @@
+use std::io;
+
+use ::{Request, Command, Error};
+use extras::{Extras, TapConnect, TapFlags};
+
+#[test]
+fn test_flags() {
+    let mut flags = TapFlags::BACKFILL | TapFlags::CHECKPOINT;
+    assert_eq!(flags.bits(), 0x41);
+    flags |= TapFlags::REGISTERED_CLIENT;
+    flags.remove(TapFlags::BACKFILL);
+    assert_eq!(flags, TapFlags::from_bits(0xc0));
+    assert!(TapFlags::empty().is_empty());
+}
+
+#[test]
+fn test_write() {
+    let extra = TapConnect::new(TapFlags::TAKEOVER | TapFlags::SUPPORT_ACK);
+    let mut raw: Vec<u8> = vec![];
+    extra.write(&mut raw).unwrap();
+    assert_eq!(&[0x00, 0x00, 0x00, 0x18][..], &raw[..]);
+    assert!(extra.body().unwrap().is_empty());
+}
+
+#[test]
+fn test_body() {
+    let extra = TapConnect::build()
+        .backfill(0x0102)
+        .vbuckets(vec![1, 0x0203])
+        .finish();
+
+    assert_eq!(extra.flags(), TapFlags::BACKFILL | TapFlags::LIST_VBUCKETS);
+    let body = extra.body().unwrap();
+    assert_eq!(&body[..], &[
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x02,
+        0x00, 0x02, 0x00, 0x01, 0x02, 0x03,
+    ][..]);
+
+    let mut parsed = TapConnect::read(&mut io::Cursor::new(&[0x00, 0x00, 0x00, 0x05][..])).unwrap();
+    parsed.read_body(&body).unwrap();
+    assert_eq!(parsed.backfill(), Some(0x0102));
+    assert_eq!(parsed.vbuckets(), Some(&[1, 0x0203][..]));
+}
+
+#[test]
+fn test_invalid_body() {
+    let mut extra = TapConnect::new(TapFlags::LIST_VBUCKETS);
+    assert!(extra.read_body(&[0x00, 0x02, 0x00, 0x01]).is_err());
+    assert!(extra.read_body(&[0x00, 0x00, 0x00]).is_err());
+    assert!(TapConnect::new(TapFlags::BACKFILL).read_body(&[0x00; 4]).is_err());
+}
+
+#[test]
+fn test_flags_without_body() {
+    let extra = TapConnect::build().flags(TapFlags::BACKFILL).finish();
+    match extra.body() {
+        Err(Error::InvalidExtras(Command::TapConnect)) => {},
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert!(extra.write(&mut vec![]).is_err());
+    assert!(Request::tap_connect(Some("n"), extra).is_err());
+
+    let mut extra = TapConnect::build().vbuckets(vec![1]).finish();
+    extra.set_flags(TapFlags::DUMP);
+    assert!(extra.body().is_err());
+    assert!(Request::tap_connect(Some("n"), extra).is_err());
+}
+
+#[test]
+fn test_request() {
+    let extra = TapConnect::build().flags(TapFlags::DUMP).backfill(5).finish();
+    let request = Request::tap_connect(Some("node"), extra).unwrap();
+
+    let mut raw: Vec<u8> = vec![];
+    request.write(&mut raw).unwrap();
+    assert_eq!(raw[1], 0x40);
+    assert_eq!(raw[4], 0x04);
+    assert_eq!(&raw[24..28], &[0x00, 0x00, 0x00, 0x03]);
+    assert_eq!(&raw[28..32], b"node");
+    assert_eq!(&raw[32..], &[0, 0, 0, 0, 0, 0, 0, 5]);
+
+    assert!(Request::new(Command::Touch).set_extras(Some(TapConnect::default())).is_err());
+}
@@ -0,0 +1,49 @@
+use std::io;
+use std::time::Duration;
+
+use ::{Command, Error};
+use extras::{Extras, Expiry, Touch, GatQ};
+
+#[test]
+fn test_blank() {
+    let extra = Touch::default();
+    let mut raw: Vec<u8> = Vec::with_capacity(4);
+    extra.write(&mut raw).unwrap();
+    assert_eq!(Expiry::Never, extra.expiration());
+    assert_eq!(&[0; 4][..], &raw[..]);
+}
+
+#[test]
+fn test_expiration() {
+    let extra = Touch::build().expiration(360_u32).finish();
+    let mut raw: Vec<u8> = Vec::with_capacity(4);
+    extra.write(&mut raw).unwrap();
+    assert_eq!(Expiry::Relative(Duration::from_secs(360)), extra.expiration());
+    assert_eq!(&[0x00, 0x00, 0x01, 0x68][..], &raw[..]);
+}
+
+#[test]
+fn test_read() {
+    let extra = GatQ::read(&mut io::Cursor::new(&[0x00, 0x00, 0x0e, 0x10][..])).unwrap();
+    assert_eq!(Expiry::Relative(Duration::from_secs(3600)), extra.expiration());
+}
+
+#[test]
+fn test_commands() {
+    assert!(Touch::request_commands().contains(&Command::Touch));
+    assert!(Touch::request_commands().contains(&Command::Gat));
+    assert!(Touch::request_commands().contains(&Command::GatQ));
+    assert!(Touch::response_commands().is_empty());
+}
+
+#[test]
+fn test_read_invalid_length() {
+    match Touch::read(&mut io::Cursor::new(&[0x00, 0x0e][..])) {
+        Err(Error::InvalidLength { expected: 4, actual: 2 }) => {},
+        _ => panic!("Short extras should be reported"),
+    }
+    match Touch::read(&mut io::Cursor::new(&[0x00; 5][..])) {
+        Err(Error::InvalidLength { expected: 4, actual: 5 }) => {},
+        _ => panic!("Oversized extras should be reported"),
+    }
+}
@@ -0,0 +1,35 @@
+use std::io;
+
+use ::{Request, Command, VBucketState};
+use extras::{Extras, SetVBucket};
+
+#[test]
+fn test_state() {
+    let extra = SetVBucket::build().state(VBucketState::Dead).finish();
+    let mut raw: Vec<u8> = vec![];
+    extra.write(&mut raw).unwrap();
+    assert_eq!(VBucketState::Dead, extra.state());
+    assert_eq!(&[0x00, 0x00, 0x00, 0x04][..], &raw[..]);
+}
+
+#[test]
+fn test_read() {
+    let extra = SetVBucket::read(&mut io::Cursor::new(&[0x00, 0x00, 0x00, 0x02][..])).unwrap();
+    assert_eq!(VBucketState::Replica, extra.state());
+
+    assert!(SetVBucket::read(&mut io::Cursor::new(&[0x00, 0x00, 0x00, 0x05][..])).is_err());
+}
+
+#[test]
+fn test_request() {
+    let request = Request::set_vbucket(42, VBucketState::Pending).unwrap();
+
+    let mut raw: Vec<u8> = vec![];
+    request.write(&mut raw).unwrap();
+    assert_eq!(raw[1], 0x3d);
+    assert_eq!(raw[4], 0x04);
+    assert_eq!(&raw[6..8], &[0x00, 0x2a]);
+    assert_eq!(&raw[24..], &[0x00, 0x00, 0x00, 0x03]);
+
+    assert!(Request::new(Command::GetVBucket).set_extras(Some(SetVBucket::default())).is_err());
+}
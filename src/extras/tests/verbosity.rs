@@ -0,0 +1,32 @@
+use std::io;
+
+use ::{Request, Command};
+use extras::{Extras, Verbosity};
+
+#[test]
+fn test_level() {
+    let extra = Verbosity::build().level(2).finish();
+    let mut raw: Vec<u8> = vec![];
+    extra.write(&mut raw).unwrap();
+    assert_eq!(2, extra.level());
+    assert_eq!(&[0x00, 0x00, 0x00, 0x02][..], &raw[..]);
+}
+
+#[test]
+fn test_read() {
+    let extra = Verbosity::read(&mut io::Cursor::new(&[0x00, 0x00, 0x00, 0x01][..])).unwrap();
+    assert_eq!(1, extra.level());
+}
+
+#[test]
+fn test_request() {
+    let mut request = Request::new(Command::Verbosity);
+    request.set_extras(Some(Verbosity::new(1))).unwrap();
+    assert!(Request::new(Command::Flush).set_extras(Some(Verbosity::new(1))).is_err());
+
+    let mut raw: Vec<u8> = vec![];
+    request.write(&mut raw).unwrap();
+    assert_eq!(raw[1], 0x1b);
+    assert_eq!(raw[4], 0x04);
+    assert_eq!(&raw[24..], &[0x00, 0x00, 0x00, 0x01]);
+}
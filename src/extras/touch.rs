@@ -0,0 +1,130 @@
+use std::fmt;
+use std::default::Default;
+
+use bytes::{Buf, BufMut};
+use byteorder::NetworkEndian;
+
+use super::{Extras, check_length, Expiration, Expiry, Clock};
+use ::{Command, Error};
+
+/// Extras container for `Touch` requests.
+///
+/// Since `Gat` and `GatQ` requests use the same format,
+/// [Gat](type.Gat.html) and [GatQ](type.GatQ.html) type aliases can be used
+/// in order to provide consistent interface.
+///
+/// # Examples
+///
+/// ```rust
+/// use memcache_proto::extras::Touch;
+///
+/// let mut extras = Touch::default();
+/// extras.set_expiration(60_u32);
+/// ```
+///
+/// With builder interface:
+///
+/// ```rust
+/// use memcache_proto::extras::Touch;
+///
+/// let extras = Touch::build()
+///     .expiration(60_u32)
+///     .finish();
+/// ```
+pub struct Touch {
+    expiration: Expiry,
+}
+
+/// Extras container for `Gat` requests.
+///
+/// It is an alias for [Touch](struct.Touch.html) struct,
+/// see [the module documentation](struct.Touch.html) for more.
+pub type Gat = Touch;
+
+/// Extras container for `GatQ` requests.
+///
+/// It is an alias for [Touch](struct.Touch.html) struct,
+/// see [the module documentation](struct.Touch.html) for more.
+pub type GatQ = Touch;
+
+impl Touch {
+
+    pub fn new<T: Expiration>(expiration: T) -> Self {
+        Self {
+            expiration: expiration.as_expiry(),
+        }
+    }
+
+    pub fn build() -> TouchBuilder {
+        TouchBuilder(Touch::default())
+    }
+
+    pub fn set_expiration<T: Expiration>(&mut self, value: T) {
+        self.expiration = value.as_expiry();
+    }
+
+    pub fn expiration(&self) -> Expiry {
+        self.expiration
+    }
+}
+
+pub struct TouchBuilder(Touch);
+
+impl TouchBuilder {
+
+    pub fn expiration<T: Expiration>(mut self, expiration: T) -> Self {
+        self.0.set_expiration(expiration);
+        self
+    }
+
+    pub fn finish(self) -> Touch {
+        self.0
+    }
+
+}
+
+impl Extras for Touch {
+    const LENGTH: usize = 4;
+
+    fn request_commands() -> &'static [Command] {
+        &[Command::Touch, Command::Gat, Command::GatQ]
+    }
+
+    fn response_commands() -> &'static [Command] {
+        &[]
+    }
+
+    fn read<T: Buf>(buf: &mut T) -> Result<Self, Error> {
+        check_length(buf, Self::LENGTH)?;
+        Ok(Self {
+            expiration: Expiry::from_seconds(buf.get_u32::<NetworkEndian>()),
+        })
+    }
+
+    fn write_with<T: BufMut, C: Clock>(&self, buf: &mut T, clock: &C) -> Result<(), Error> {
+        let expiration = self.expiration.to_seconds(clock)?;
+        buf.put_u32::<NetworkEndian>(expiration);
+        Ok(())
+    }
+
+    fn expiry(&self) -> Option<Expiry> {
+        Some(self.expiration)
+    }
+
+}
+
+impl Default for Touch {
+    fn default() -> Self {
+        Self {
+            expiration: Expiry::Never,
+        }
+    }
+}
+
+impl fmt::Debug for Touch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Touch")
+            .field("expiration", &self.expiration())
+            .finish()
+    }
+}
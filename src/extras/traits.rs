@@ -1,5 +1,8 @@
+use std::u32;
 use std::time::{SystemTime, Duration, UNIX_EPOCH};
 
+use ::Error;
+
 // Expiration times are specified in unsigned integer seconds.
 // They can be set from 0, meaning "never expire", to 30 days (60*60*24*30).
 // Any time higher than 30 days is interpreted as a unix timestamp date.
@@ -7,53 +10,168 @@ use std::time::{SystemTime, Duration, UNIX_EPOCH};
 
 pub const MAX_SECONDS: u32 = 60 * 60 * 24 * 30;
 
+/// Source of the current time, used for the relative expirations longer than 30 days.
+///
+/// Custom implementations can be used in order to get deterministic conversions.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+}
+
+/// Clock based on the `SystemTime::now()`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// Explicit expiration model.
+///
+/// Reference: https://github.com/memcached/memcached/wiki/Programming#expiration
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Expiry {
+    /// Item never expires.
+    Never,
+    /// Item expires after the given time from now.
+    Relative(Duration),
+    /// Item expires at the given time.
+    At(SystemTime),
+}
+
+impl Expiry {
+
+    /// Parse the protocol seconds value.
+    pub fn from_seconds(value: u32) -> Expiry {
+        match value {
+            0 => Expiry::Never,
+            value if value <= MAX_SECONDS => Expiry::Relative(Duration::from_secs(value as u64)),
+            value => Expiry::At(UNIX_EPOCH + Duration::from_secs(value as u64)),
+        }
+    }
+
+    /// Convert into the protocol seconds value.
+    ///
+    /// Relative expirations up to 30 days are sent as is,
+    /// longer ones are converted into a unix timestamp with a help of `clock`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use memcache_proto::extras::{Expiry, SystemClock};
+    ///
+    /// let expiry = Expiry::Relative(Duration::from_secs(3600));
+    /// assert_eq!(expiry.to_seconds(&SystemClock).unwrap(), 3600);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if relative expiration is zero, since `0` means "never expire" for the server,
+    /// if timestamp is before the unix epoch, does not fit into `u32` (after year 2106)
+    /// or is so close to the epoch that server would treat it as a relative value.
+    pub fn to_seconds<C: Clock>(&self, clock: &C) -> Result<u32, Error> {
+        match *self {
+            Expiry::Never => Ok(0),
+            Expiry::Relative(duration) if duration == Duration::from_secs(0) => {
+                Err(Error::InvalidExpiration(*self))
+            },
+            Expiry::Relative(duration) if ceil_seconds(duration) <= MAX_SECONDS as u64 => {
+                Ok(ceil_seconds(duration) as u32)
+            },
+            Expiry::Relative(duration) => {
+                let at = clock.now().checked_add(duration)
+                    .ok_or_else(|| Error::InvalidExpiration(*self))?;
+                Expiry::At(at).to_seconds(clock).map_err(|_| Error::InvalidExpiration(*self))
+            },
+            Expiry::At(at) => {
+                let timestamp = at.duration_since(UNIX_EPOCH)
+                    .map_err(|_| Error::InvalidExpiration(*self))?
+                    .as_secs();
+                if timestamp <= MAX_SECONDS as u64 || timestamp > u32::MAX as u64 {
+                    return Err(Error::InvalidExpiration(*self));
+                }
+                Ok(timestamp as u32)
+            },
+        }
+    }
+}
+
+// Sub-second durations should not become a zero, "never expire" value
+fn ceil_seconds(duration: Duration) -> u64 {
+    if duration.subsec_nanos() > 0 {
+        duration.as_secs() + 1
+    } else {
+        duration.as_secs()
+    }
+}
+
 /// Expiration timeout
 ///
 /// Reference: https://github.com/memcached/memcached/wiki/Programming#expiration
 pub trait Expiration {
-    /// Convert object into a seconds timeout.
+    /// Convert object into an explicit expiration.
     ///
     /// Implementations should not bother about wrapping around magic "30 days" limit,
-    /// just return seconds value.
-    fn as_expiration(&self) -> u32;
-
-    fn get_timeout(&self) -> u32 {
-        let value = self.as_expiration();
-        if value > MAX_SECONDS {
-            let now = SystemTime::now()
-                // TODO: Handle `.unwrap()` error
-                .duration_since(UNIX_EPOCH).unwrap();
-            now.checked_add(Duration::new(value as u64, 0))
-                // TODO: Possible value truncation
-                .unwrap().as_secs() as u32
-        } else {
-            value
-        }
+    /// relative values are converted into timestamps when needed.
+    fn as_expiry(&self) -> Expiry;
+
+    /// Convert object into a protocol seconds value with a system clock.
+    fn get_timeout(&self) -> Result<u32, Error> {
+        self.get_timeout_with(&SystemClock)
     }
-}
 
+    /// Convert object into a protocol seconds value with a custom clock.
+    fn get_timeout_with<C: Clock>(&self, clock: &C) -> Result<u32, Error> {
+        self.as_expiry().to_seconds(clock)
+    }
+}
 
 impl Expiration for u8 {
-    fn as_expiration(&self) -> u32 {
-        *self as u32
+    fn as_expiry(&self) -> Expiry {
+        (*self as u32).as_expiry()
     }
 }
 
 impl Expiration for u16 {
-    fn as_expiration(&self) -> u32 {
-        *self as u32
+    fn as_expiry(&self) -> Expiry {
+        (*self as u32).as_expiry()
     }
 }
 
 impl Expiration for u32 {
-    fn as_expiration(&self) -> u32 {
-        *self as u32
+    fn as_expiry(&self) -> Expiry {
+        match *self {
+            0 => Expiry::Never,
+            value => Expiry::Relative(Duration::from_secs(value as u64)),
+        }
     }
 }
 
 impl Expiration for Duration {
-    fn as_expiration(&self) -> u32 {
-        // TODO: Possible value truncation
-        self.as_secs() as u32
+    fn as_expiry(&self) -> Expiry {
+        Expiry::Relative(*self)
+    }
+}
+
+impl Expiration for Option<Duration> {
+    fn as_expiry(&self) -> Expiry {
+        match *self {
+            Some(duration) => Expiry::Relative(duration),
+            None => Expiry::Never,
+        }
+    }
+}
+
+impl Expiration for SystemTime {
+    fn as_expiry(&self) -> Expiry {
+        Expiry::At(*self)
+    }
+}
+
+impl Expiration for Expiry {
+    fn as_expiry(&self) -> Expiry {
+        *self
     }
 }
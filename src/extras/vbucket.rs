@@ -0,0 +1,120 @@
+use std::fmt;
+use std::default::Default;
+
+use bytes::{Buf, BufMut};
+use byteorder::NetworkEndian;
+use enum_primitive::FromPrimitive;
+
+use super::{Extras, Clock, check_length};
+use ::{Command, Error, VBucketState};
+
+/// Extras container for `SetVBucket` requests.
+///
+/// vBucket id itself is sent in the request header,
+/// see [Request::set_vbucket_id](../struct.Request.html#method.set_vbucket_id).
+///
+/// # Examples
+///
+/// ```rust
+/// use memcache_proto::VBucketState;
+/// use memcache_proto::extras::SetVBucket;
+///
+/// let mut extras = SetVBucket::default();
+/// extras.set_state(VBucketState::Replica);
+/// ```
+///
+/// With builder interface:
+///
+/// ```rust
+/// use memcache_proto::VBucketState;
+/// use memcache_proto::extras::SetVBucket;
+///
+/// let extras = SetVBucket::build()
+///     .state(VBucketState::Pending)
+///     .finish();
+/// ```
+pub struct SetVBucket {
+    state: VBucketState,
+}
+
+impl SetVBucket {
+
+    pub fn new(state: VBucketState) -> Self {
+        Self {
+            state: state,
+        }
+    }
+
+    pub fn build() -> SetVBucketBuilder {
+        SetVBucketBuilder(SetVBucket::default())
+    }
+
+    pub fn set_state(&mut self, value: VBucketState) {
+        self.state = value;
+    }
+
+    pub fn state(&self) -> VBucketState {
+        self.state
+    }
+}
+
+pub struct SetVBucketBuilder(SetVBucket);
+
+impl SetVBucketBuilder {
+
+    pub fn state(mut self, state: VBucketState) -> Self {
+        self.0.set_state(state);
+        self
+    }
+
+    pub fn finish(self) -> SetVBucket {
+        self.0
+    }
+
+}
+
+impl Extras for SetVBucket {
+    const LENGTH: usize = 4;
+
+    fn request_commands() -> &'static [Command] {
+        &[Command::SetVBucket]
+    }
+
+    fn response_commands() -> &'static [Command] {
+        // `GetVBucket` state is sent in the response body instead
+        &[]
+    }
+
+    fn read<T: Buf>(buf: &mut T) -> Result<Self, Error> {
+        check_length(buf, Self::LENGTH)?;
+        let state = buf.get_u32::<NetworkEndian>();
+        let state = VBucketState::from_u32(state)
+            .ok_or_else(|| Error::UnknownVBucketState(state))?;
+
+        Ok(Self {
+            state: state,
+        })
+    }
+
+    fn write_with<T: BufMut, C: Clock>(&self, buf: &mut T, _: &C) -> Result<(), Error> {
+        buf.put_u32::<NetworkEndian>(self.state as u32);
+        Ok(())
+    }
+
+}
+
+impl Default for SetVBucket {
+    fn default() -> Self {
+        Self {
+            state: VBucketState::Active,
+        }
+    }
+}
+
+impl fmt::Debug for SetVBucket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SetVBucket")
+            .field("state", &self.state())
+            .finish()
+    }
+}
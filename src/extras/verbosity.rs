@@ -0,0 +1,109 @@
+use std::fmt;
+use std::default::Default;
+
+use bytes::{Buf, BufMut};
+use byteorder::NetworkEndian;
+
+use super::{Extras, Clock, check_length};
+use ::{Command, Error};
+
+/// Extras container for `Verbosity` requests.
+///
+/// # Examples
+///
+/// ```rust
+/// use memcache_proto::extras::Verbosity;
+///
+/// let mut extras = Verbosity::default();
+/// extras.set_level(2);
+/// ```
+///
+/// With builder interface:
+///
+/// ```rust
+/// use memcache_proto::extras::Verbosity;
+///
+/// let extras = Verbosity::build()
+///     .level(2)
+///     .finish();
+/// ```
+pub struct Verbosity {
+    level: u32,
+}
+
+impl Verbosity {
+
+    pub fn new(level: u32) -> Self {
+        Self {
+            level: level,
+        }
+    }
+
+    pub fn build() -> VerbosityBuilder {
+        VerbosityBuilder(Verbosity::default())
+    }
+
+    pub fn set_level(&mut self, value: u32) {
+        self.level = value;
+    }
+
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+}
+
+pub struct VerbosityBuilder(Verbosity);
+
+impl VerbosityBuilder {
+
+    pub fn level(mut self, level: u32) -> Self {
+        self.0.set_level(level);
+        self
+    }
+
+    pub fn finish(self) -> Verbosity {
+        self.0
+    }
+
+}
+
+impl Extras for Verbosity {
+    const LENGTH: usize = 4;
+
+    fn request_commands() -> &'static [Command] {
+        &[Command::Verbosity]
+    }
+
+    fn response_commands() -> &'static [Command] {
+        &[]
+    }
+
+    fn read<T: Buf>(buf: &mut T) -> Result<Self, Error> {
+        check_length(buf, Self::LENGTH)?;
+        Ok(Self {
+            level: buf.get_u32::<NetworkEndian>(),
+        })
+    }
+
+    fn write_with<T: BufMut, C: Clock>(&self, buf: &mut T, _: &C) -> Result<(), Error> {
+        buf.put_u32::<NetworkEndian>(self.level);
+        Ok(())
+    }
+
+}
+
+impl Default for Verbosity {
+    fn default() -> Self {
+        Self {
+            level: 0,
+        }
+    }
+}
+
+impl fmt::Debug for Verbosity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Verbosity")
+            .field("level", &self.level())
+            .finish()
+    }
+}
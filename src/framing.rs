@@ -0,0 +1,88 @@
+use std::io::{self, Read};
+use std::cmp;
+
+use byteorder::{ByteOrder, NetworkEndian};
+use enum_primitive::FromPrimitive;
+
+use super::{Magic, Error};
+
+/// Length of the fixed-size header, same for requests and responses.
+pub const HEADER_SIZE: usize = 24;
+
+/// Size of the single read from the stream.
+pub const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Fill the `header` buffer from `src`.
+///
+/// Returns `false` if `src` reached EOF before the first byte,
+/// which means that the peer closed connection between the packets.
+pub fn read_header<R: Read>(src: &mut R, header: &mut [u8]) -> Result<bool, Error> {
+    let mut received = 0;
+    while received < header.len() {
+        match src.read(&mut header[received..]) {
+            Ok(0) if received == 0 => return Ok(false),
+            Ok(0) => return Err(truncated(received, header.len())),
+            Ok(n) => received += n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {},
+            Err(e) => return Err(Error::Io(e)),
+        }
+    }
+
+    Ok(true)
+}
+
+/// Check the magic byte of the request `header` and return its body length.
+///
+/// Body length is limited by `max_body_length`, so the rest of the packet
+/// is not read from the untrusted peer before it is known to be acceptable.
+pub fn check_request_header(header: &[u8], max_body_length: usize) -> Result<usize, Error> {
+    let magic = Magic::from_u8(header[0]).ok_or_else(|| Error::UnknownMagic(header[0]))?;
+    if magic != Magic::Request {
+        return Err(Error::UnexpectedMagic(magic));
+    }
+    let body_length = NetworkEndian::read_u32(&header[8..12]) as usize;
+    if body_length > max_body_length {
+        return Err(Error::BodyLimitExceeded {
+            length: body_length,
+            limit: max_body_length,
+        });
+    }
+
+    Ok(body_length)
+}
+
+/// Read exactly `length` body bytes from `src`, which are following the `header_length` bytes.
+pub fn read_body<R: Read>(src: &mut R, header_length: usize, length: usize) -> Result<Vec<u8>, Error> {
+    let mut body = Vec::new();
+    let mut received = 0;
+    while received < length {
+        grow_buffer(&mut body, received, length);
+        match src.read(&mut body[received..]) {
+            Ok(0) => return Err(truncated(header_length + received, header_length + length)),
+            Ok(n) => received += n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {},
+            Err(e) => return Err(Error::Io(e)),
+        }
+    }
+
+    Ok(body)
+}
+
+/// Make room in `buf` for the next read, if the first `filled` bytes are already used.
+///
+/// Buffer grows by `CHUNK_SIZE` along with the received data up to the `target` length,
+/// so bogus body length does not cause a huge allocation upfront.
+pub fn grow_buffer(buf: &mut Vec<u8>, filled: usize, target: usize) {
+    if buf.len() == filled {
+        let length = cmp::min(target, filled + CHUNK_SIZE);
+        buf.resize(length, 0);
+    }
+}
+
+/// Error for the stream, which ended in the middle of a packet.
+pub fn truncated(received: usize, expected: usize) -> Error {
+    Error::Io(io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        format!("stream ended after {} of {} packet bytes", received, expected),
+    ))
+}
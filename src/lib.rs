@@ -7,17 +7,40 @@
 #[macro_use] extern crate enum_primitive;
 extern crate byteorder;
 extern crate bytes;
+#[cfg(feature = "tokio")]
+extern crate tokio_codec;
+#[cfg(feature = "futures")]
+extern crate futures_io;
+#[cfg(feature = "futures")]
+extern crate futures_core;
+#[cfg(feature = "futures")]
+extern crate futures_sink;
 
 mod command;
 mod request;
 mod response;
 mod errors;
+mod tap;
+mod range;
+mod stats;
+mod framing;
+#[cfg(feature = "tokio")]
+mod codec;
+#[cfg(feature = "futures")]
+mod async_io;
 pub mod extras;
 
 pub use command::Command;
-pub use request::{Request, RequestBuilder};
-pub use response::{Response, Status};
-pub use errors::{ResponseError};
+pub use request::{Request, RequestBuilder, RequestRef, VectoredRequest, MAX_KEY_LENGTH, DEFAULT_MAX_BODY_LENGTH};
+pub use response::{Response, ResponseBuilder, ResponseDecoder, Status};
+pub use errors::Error;
+pub use tap::{TapMessage, TapMessageFlags};
+pub use range::{RangeCollector, RangeItem};
+pub use stats::{StatsCollector, Stats, StatGroup};
+#[cfg(feature = "tokio")]
+pub use codec::{MemcacheClientCodec, MemcacheServerCodec};
+#[cfg(feature = "futures")]
+pub use async_io::{WriteRequest, ReadResponse, ClientTransport};
 
 enum_from_primitive! {
     /// Magic byte values for protocol packets.
@@ -40,5 +63,16 @@ enum_from_primitive! {
     }
 }
 
+enum_from_primitive! {
+    /// vBucket states, carried by `SetVBucket` requests and `GetVBucket` responses.
+    #[derive(Debug, PartialEq, Copy, Clone)]
+    pub enum VBucketState {
+        Active = 0x01,
+        Replica = 0x02,
+        Pending = 0x03,
+        Dead = 0x04,
+    }
+}
+
 #[cfg(test)]
 mod tests;
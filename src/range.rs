@@ -0,0 +1,139 @@
+use std::fmt;
+
+use bytes::Bytes;
+
+use super::{Command, Response, Status, Error};
+
+/// Single item of the `RGet` responses stream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeItem {
+    key: Bytes,
+    value: Option<Bytes>,
+    flags: Option<u32>,
+}
+
+impl RangeItem {
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    pub fn value(&self) -> Option<&[u8]> {
+        self.value.as_ref().map(|value| &value[..])
+    }
+
+    /// Item flags, if server sent them.
+    pub fn flags(&self) -> Option<u32> {
+        self.flags
+    }
+}
+
+/// Collector for the `RGet` responses.
+///
+/// Server responds to the `RGet` request with a separate response for each found item,
+/// stream is terminated by a response without a key.
+/// `KeyNotFound` status is treated as an empty range.
+///
+/// # Examples
+///
+/// ```rust
+/// use memcache_proto::{Command, Response, RangeCollector};
+///
+/// let mut collector = RangeCollector::new();
+///
+/// let item = Response::build(Command::RGet).key(Some("a")).value(Some("1")).finish().unwrap();
+/// assert!(!collector.push(item).unwrap());
+///
+/// let end = Response::build(Command::RGet).finish().unwrap();
+/// assert!(collector.push(end).unwrap());
+///
+/// assert_eq!(collector.items()[0].key(), b"a");
+/// ```
+pub struct RangeCollector {
+    items: Vec<RangeItem>,
+    done: bool,
+}
+
+impl RangeCollector {
+
+    pub fn new() -> RangeCollector {
+        RangeCollector {
+            items: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// Add next response of the stream.
+    ///
+    /// Returns `true` when the stream is finished and no more responses are expected.
+    ///
+    /// # Errors
+    ///
+    /// Fails if response is not an `RGet` one, if server responded with an error status
+    /// or if item flags are malformed.
+    ///
+    /// Responses received after the terminating one are rejected with `Error::UnexpectedCommand`,
+    /// so items of the next range are never merged into this one.
+    pub fn push(&mut self, response: Response) -> Result<bool, Error> {
+        if self.done || *response.command() != Command::RGet {
+            return Err(Error::UnexpectedCommand(*response.command()));
+        }
+
+        match *response.status() {
+            Status::Ok => {},
+            Status::KeyNotFound => {
+                self.done = true;
+                return Ok(true);
+            },
+            status => return Err(Error::Status(status)),
+        }
+
+        let key = match response.key_bytes() {
+            Some(key) => key,
+            None => {
+                self.done = true;
+                return Ok(true);
+            },
+        };
+        let flags = match response.extras() {
+            Some(..) => Some(response.flags()?),
+            None => None,
+        };
+
+        self.items.push(RangeItem {
+            key: key,
+            value: response.value_bytes(),
+            flags: flags,
+        });
+
+        Ok(false)
+    }
+
+    /// Check if stream terminating response was received.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn items(&self) -> &[RangeItem] {
+        &self.items
+    }
+
+    pub fn into_items(self) -> Vec<RangeItem> {
+        self.items
+    }
+}
+
+impl Default for RangeCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for RangeCollector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RangeCollector")
+            .field("items", &self.items)
+            .field("done", &self.done)
+            .finish()
+    }
+}
@@ -0,0 +1,249 @@
+use std::io;
+use std::u8;
+use std::u32;
+use std::fmt;
+use std::convert::Into;
+
+use bytes::BufMut;
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{Magic, Command, DataType, Request, Error, HEADER_SIZE, MAX_KEY_LENGTH};
+use super::vectored::VectoredRequest;
+use ::extras::{Expiry, Clock, SystemClock};
+
+// Borrowed extras head and the serialized expiration tail
+type SplitExtras<'a> = (Option<&'a [u8]>, Option<[u8; 4]>);
+
+/// Memcached request, which borrows key, value and extras instead of copying them.
+///
+/// Encoding is the same as for the owned [Request](struct.Request.html),
+/// so it can be used to serialize data straight from the application buffers.
+///
+/// # Examples
+///
+/// ```rust
+/// use memcache_proto::{RequestRef, Command};
+///
+/// let value = vec![0x61; 512 * 1024];
+/// let mut request = RequestRef::new(Command::Append);
+/// request.set_key(Some("Hello"));
+/// request.set_value(Some(&value));
+///
+/// let mut buf: Vec<u8> = Vec::with_capacity(request.len());
+/// request.write(&mut buf).unwrap();
+/// ```
+#[derive(Clone, Copy)]
+pub struct RequestRef<'a> {
+    magic: Magic,
+    opcode: Command,
+    data_type: DataType,
+    vbucket_id: u16,
+    opaque: u32,
+    cas: u64,
+
+    // body
+    extras: Option<&'a [u8]>,
+    key: Option<&'a [u8]>,
+    value: Option<&'a [u8]>,
+
+    // Expiration from the owned request extras, replaces their last 4 bytes when encoded
+    expiration: Option<Expiry>,
+}
+
+impl<'a> RequestRef<'a> {
+
+    /// Create new RequestRef with all fields blank.
+    pub fn new(command: Command) -> RequestRef<'a> {
+        RequestRef {
+            magic: Magic::Request,
+            opcode: command.normalize(),
+            data_type: DataType::RawBytes,
+            vbucket_id: 0,
+            opaque: 0,
+            cas: 0,
+            extras: None,
+            key: None,
+            value: None,
+            expiration: None,
+        }
+    }
+
+    /// Provide key field.
+    pub fn set_key<T: AsRef<[u8]> + ?Sized>(&mut self, key: Option<&'a T>) {
+        self.key = key.map(|key| key.as_ref());
+    }
+
+    /// Provide value field.
+    pub fn set_value<T: AsRef<[u8]> + ?Sized>(&mut self, value: Option<&'a T>) {
+        self.value = value.map(|value| value.as_ref());
+    }
+
+    /// Provide already serialized extras field.
+    ///
+    /// Unlike [Request::set_extras](struct.Request.html#method.set_extras)
+    /// extras are not checked against the request command.
+    pub fn set_raw_extras<T: AsRef<[u8]> + ?Sized>(&mut self, extras: Option<&'a T>) {
+        self.extras = extras.map(|extras| extras.as_ref());
+    }
+
+    /// Provide virtual bucket ID field.
+    pub fn set_vbucket_id<T: Into<u16>>(&mut self, value: T) {
+        self.vbucket_id = value.into();
+    }
+
+    /// Provide opaque field.
+    pub fn set_opaque<T: Into<u32>>(&mut self, value: T) {
+        self.opaque = value.into();
+    }
+
+    /// Provide CAS field.
+    pub fn set_cas<T: Into<u64>>(&mut self, value: T) {
+        self.cas = value.into();
+    }
+
+    /// Write serialized request as a bytes into `T`
+    ///
+    /// See [Request::write](struct.Request.html#method.write) for details.
+    pub fn write<T: BufMut>(&self, out: &mut T) -> Result<(), Error> {
+        self.write_with(out, &SystemClock)
+    }
+
+    /// Write serialized request as a bytes into `T` with a custom `clock`,
+    /// see [Request::write_with](struct.Request.html#method.write_with).
+    pub fn write_with<T: BufMut, C: Clock>(&self, out: &mut T, clock: &C) -> Result<(), Error> {
+        let header = self.encode_header()?;
+        let (extras, expiration) = self.split_extras(clock)?;
+        let required = self.len();
+        if out.remaining_mut() < required {
+            return Err(Error::NotEnoughCapacity {
+                required: required,
+                remaining: out.remaining_mut(),
+            });
+        }
+
+        out.put_slice(&header);
+
+        if let Some(extras) = extras {
+            out.put_slice(extras);
+        }
+
+        if let Some(expiration) = expiration {
+            out.put_slice(&expiration);
+        }
+
+        if let Some(key) = self.key {
+            out.put_slice(key);
+        }
+
+        if let Some(value) = self.value {
+            out.put_slice(value);
+        }
+
+        Ok(())
+    }
+
+    /// Serialize only the request header, extras, key and value stay borrowed,
+    /// see [Request::encode_vectored](struct.Request.html#method.encode_vectored).
+    pub fn encode_vectored(&self) -> Result<VectoredRequest<'a>, Error> {
+        self.encode_vectored_with(&SystemClock)
+    }
+
+    /// Serialize only the request header with a custom `clock`,
+    /// see [Request::encode_vectored_with](struct.Request.html#method.encode_vectored_with).
+    pub fn encode_vectored_with<C: Clock>(&self, clock: &C) -> Result<VectoredRequest<'a>, Error> {
+        let header = self.encode_header()?;
+        let (extras, expiration) = self.split_extras(clock)?;
+        Ok(VectoredRequest::new(header, extras, expiration, self.key, self.value))
+    }
+
+    /// Write serialized request into the blocking `std::io` writer,
+    /// see [Request::write_to](struct.Request.html#method.write_to).
+    pub fn write_to<W: io::Write>(&self, out: &mut W) -> Result<(), Error> {
+        let mut buf = Vec::with_capacity(self.len());
+        self.write(&mut buf)?;
+        out.write_all(&buf)?;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        HEADER_SIZE
+            + self.extras.map_or(0, |extras| extras.len())
+            + self.key.map_or(0, |key| key.len())
+            + self.value.map_or(0, |value| value.len())
+    }
+
+    // Split extras into the borrowed head and the expiration converted with the `clock`
+    fn split_extras<C: Clock>(&self, clock: &C) -> Result<SplitExtras<'a>, Error> {
+        match (self.extras, self.expiration) {
+            (Some(extras), Some(expiration)) if extras.len() >= 4 => {
+                let mut tail = [0; 4];
+                NetworkEndian::write_u32(&mut tail, expiration.to_seconds(clock)?);
+                Ok((Some(&extras[..extras.len() - 4]), Some(tail)))
+            },
+            (extras, _) => Ok((extras, None)),
+        }
+    }
+
+    // Validate the request and serialize its header
+    fn encode_header(&self) -> Result<[u8; HEADER_SIZE], Error> {
+        let key_length = self.key.map_or(0, |key| key.len());
+        if key_length > MAX_KEY_LENGTH {
+            return Err(Error::KeyTooLong(key_length));
+        }
+        let extras_length = self.extras.map_or(0, |extras| extras.len());
+        if extras_length > u8::MAX as usize {
+            return Err(Error::ExtrasTooLong(extras_length));
+        }
+        let body_length = self.value.map_or(0, |value| value.len()) + key_length + extras_length;
+        if body_length > u32::MAX as usize {
+            return Err(Error::BodyTooLong(body_length));
+        }
+        Request::validate(self.opcode, extras_length, key_length,
+                          body_length - extras_length - key_length)?;
+
+        let mut header = [0; HEADER_SIZE];
+        header[0] = self.magic as u8;
+        header[1] = self.opcode.into();
+        NetworkEndian::write_u16(&mut header[2..4], key_length as u16);
+        header[4] = extras_length as u8;
+        header[5] = self.data_type as u8;
+        NetworkEndian::write_u16(&mut header[6..8], self.vbucket_id);
+        NetworkEndian::write_u32(&mut header[8..12], body_length as u32);
+        NetworkEndian::write_u32(&mut header[12..16], self.opaque);
+        NetworkEndian::write_u64(&mut header[16..24], self.cas);
+
+        Ok(header)
+    }
+}
+
+impl<'a> From<&'a Request> for RequestRef<'a> {
+    fn from(request: &'a Request) -> Self {
+        RequestRef {
+            magic: request.magic,
+            opcode: request.opcode,
+            data_type: request.data_type,
+            vbucket_id: request.vbucket_id,
+            opaque: request.opaque,
+            cas: request.cas,
+            extras: request.extras(),
+            key: request.key(),
+            value: request.value(),
+            expiration: request.expiration,
+        }
+    }
+}
+
+impl<'a> fmt::Debug for RequestRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RequestRef")
+            .field("command", &self.opcode)
+            .field("vbucket_id", &self.vbucket_id)
+            .field("opaque", &self.opaque)
+            .field("cas", &self.cas)
+            .field("key", &self.key)
+            .field("value", &self.value)
+            .field("extras", &self.extras)
+            .field("expiration", &self.expiration)
+            .finish()
+    }
+}
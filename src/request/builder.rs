@@ -1,7 +1,8 @@
 use std::fmt;
 use std::default::Default;
 
-use super::{Command, Request};
+use super::{Command, Request, Error};
+use ::extras::Extras;
 
 /// Builder, which can be used in order to create a new [Request](type.Request.html)
 /// via chain calls.
@@ -22,7 +23,7 @@ pub struct RequestBuilder(Request);
 impl RequestBuilder {
     pub fn new(command: Command) -> Self {
         RequestBuilder(Request{
-            opcode: command,
+            opcode: command.normalize(),
             ..Request::default()
         })
     }
@@ -45,12 +46,14 @@ impl RequestBuilder {
         self
     }
 
-//    /// Provide extras
-// TODO:
-//    pub fn extras<T: AsRef<[u8]>>(mut self, extras: Option<T>) -> Self {
-//        self.0.set_extras(extras);
-//        self
-//    }
+    /// Provide extras
+    ///
+    /// Fails if extras type can't be used with the request command,
+    /// see [Request::set_extras](struct.Request.html#method.set_extras).
+    pub fn extras<E: Extras>(mut self, extras: Option<E>) -> Result<Self, Error> {
+        self.0.set_extras(extras)?;
+        Ok(self)
+    }
 
     /// Provide key
     pub fn key<T: AsRef<[u8]>>(mut self, key: Option<T>) -> Self {
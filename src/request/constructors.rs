@@ -0,0 +1,369 @@
+use super::{Command, Request, Error, MAX_KEY_LENGTH};
+use ::VBucketState;
+use ::extras::{self, Expiration};
+
+/// Shortcuts for requests creation.
+///
+/// Each constructor fills the request fields required by the command opcode,
+/// so the resulting packet has the right shape without extra work.
+///
+/// Constructors which are accepting typed extras might fail,
+/// see [Request::set_extras](struct.Request.html#method.set_extras).
+impl Request {
+
+    /// Create `Get` request.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use memcache_proto::Request;
+    ///
+    /// let request = Request::get(b"Hello");
+    /// ```
+    pub fn get<K: AsRef<[u8]>>(key: K) -> Request {
+        Request::keyed(Command::Get, key)
+    }
+
+    /// Create `GetQ` request, quiet version of [Request::get](#method.get).
+    pub fn get_q<K: AsRef<[u8]>>(key: K) -> Request {
+        Request::keyed(Command::GetQ, key)
+    }
+
+    /// Create `GetK` request, response will contain the key.
+    pub fn get_k<K: AsRef<[u8]>>(key: K) -> Request {
+        Request::keyed(Command::GetK, key)
+    }
+
+    /// Create `GetKQ` request, quiet version of [Request::get_k](#method.get_k).
+    pub fn get_kq<K: AsRef<[u8]>>(key: K) -> Request {
+        Request::keyed(Command::GetKQ, key)
+    }
+
+    /// Create `Set` request.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use memcache_proto::{Request, extras};
+    ///
+    /// let request = Request::set(b"Hello", b"World", extras::Set::new(0, 3600_u32)).unwrap();
+    /// ```
+    pub fn set<K, V>(key: K, value: V, extras: extras::Set) -> Result<Request, Error>
+            where K: AsRef<[u8]>, V: AsRef<[u8]> {
+        Request::storage(Command::Set, key, value, extras)
+    }
+
+    /// Create `SetQ` request, quiet version of [Request::set](#method.set).
+    pub fn set_q<K, V>(key: K, value: V, extras: extras::Set) -> Result<Request, Error>
+            where K: AsRef<[u8]>, V: AsRef<[u8]> {
+        Request::storage(Command::SetQ, key, value, extras)
+    }
+
+    /// Create `Add` request.
+    pub fn add<K, V>(key: K, value: V, extras: extras::Add) -> Result<Request, Error>
+            where K: AsRef<[u8]>, V: AsRef<[u8]> {
+        Request::storage(Command::Add, key, value, extras)
+    }
+
+    /// Create `AddQ` request, quiet version of [Request::add](#method.add).
+    pub fn add_q<K, V>(key: K, value: V, extras: extras::Add) -> Result<Request, Error>
+            where K: AsRef<[u8]>, V: AsRef<[u8]> {
+        Request::storage(Command::AddQ, key, value, extras)
+    }
+
+    /// Create `Replace` request.
+    pub fn replace<K, V>(key: K, value: V, extras: extras::Replace) -> Result<Request, Error>
+            where K: AsRef<[u8]>, V: AsRef<[u8]> {
+        Request::storage(Command::Replace, key, value, extras)
+    }
+
+    /// Create `ReplaceQ` request, quiet version of [Request::replace](#method.replace).
+    pub fn replace_q<K, V>(key: K, value: V, extras: extras::Replace) -> Result<Request, Error>
+            where K: AsRef<[u8]>, V: AsRef<[u8]> {
+        Request::storage(Command::ReplaceQ, key, value, extras)
+    }
+
+    /// Create `Append` request.
+    pub fn append<K: AsRef<[u8]>, V: AsRef<[u8]>>(key: K, value: V) -> Request {
+        Request::keyed_value(Command::Append, key, value)
+    }
+
+    /// Create `AppendQ` request, quiet version of [Request::append](#method.append).
+    pub fn append_q<K: AsRef<[u8]>, V: AsRef<[u8]>>(key: K, value: V) -> Request {
+        Request::keyed_value(Command::AppendQ, key, value)
+    }
+
+    /// Create `Prepend` request.
+    pub fn prepend<K: AsRef<[u8]>, V: AsRef<[u8]>>(key: K, value: V) -> Request {
+        Request::keyed_value(Command::Prepend, key, value)
+    }
+
+    /// Create `PrependQ` request, quiet version of [Request::prepend](#method.prepend).
+    pub fn prepend_q<K: AsRef<[u8]>, V: AsRef<[u8]>>(key: K, value: V) -> Request {
+        Request::keyed_value(Command::PrependQ, key, value)
+    }
+
+    /// Create `Delete` request.
+    pub fn delete<K: AsRef<[u8]>>(key: K) -> Request {
+        Request::keyed(Command::Delete, key)
+    }
+
+    /// Create `DeleteQ` request, quiet version of [Request::delete](#method.delete).
+    pub fn delete_q<K: AsRef<[u8]>>(key: K) -> Request {
+        Request::keyed(Command::DeleteQ, key)
+    }
+
+    /// Create `Increment` request.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use memcache_proto::{Request, extras};
+    ///
+    /// let extras = extras::Increment::build().amount(1).finish();
+    /// let request = Request::increment(b"counter", extras).unwrap();
+    /// ```
+    pub fn increment<K: AsRef<[u8]>>(key: K, extras: extras::Increment) -> Result<Request, Error> {
+        Request::counter(Command::Increment, key, extras)
+    }
+
+    /// Create `IncrementQ` request, quiet version of [Request::increment](#method.increment).
+    pub fn increment_q<K: AsRef<[u8]>>(key: K, extras: extras::Increment) -> Result<Request, Error> {
+        Request::counter(Command::IncrementQ, key, extras)
+    }
+
+    /// Create `Decrement` request.
+    pub fn decrement<K: AsRef<[u8]>>(key: K, extras: extras::Decrement) -> Result<Request, Error> {
+        Request::counter(Command::Decrement, key, extras)
+    }
+
+    /// Create `DecrementQ` request, quiet version of [Request::decrement](#method.decrement).
+    pub fn decrement_q<K: AsRef<[u8]>>(key: K, extras: extras::Decrement) -> Result<Request, Error> {
+        Request::counter(Command::DecrementQ, key, extras)
+    }
+
+    /// Create `Touch` request.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use memcache_proto::Request;
+    ///
+    /// let request = Request::touch(b"Hello", 3600_u32).unwrap();
+    /// ```
+    pub fn touch<K: AsRef<[u8]>, T: Expiration>(key: K, expiration: T) -> Result<Request, Error> {
+        Request::touching(Command::Touch, key, expiration)
+    }
+
+    /// Create `Gat` ("get and touch") request.
+    pub fn gat<K: AsRef<[u8]>, T: Expiration>(key: K, expiration: T) -> Result<Request, Error> {
+        Request::touching(Command::Gat, key, expiration)
+    }
+
+    /// Create `GatQ` request, quiet version of [Request::gat](#method.gat).
+    pub fn gat_q<K: AsRef<[u8]>, T: Expiration>(key: K, expiration: T) -> Result<Request, Error> {
+        Request::touching(Command::GatQ, key, expiration)
+    }
+
+    /// Create `Flush` request.
+    ///
+    /// Items will be invalidated after the `expiration` delay.
+    pub fn flush<T: Expiration>(expiration: T) -> Result<Request, Error> {
+        Request::flushing(Command::Flush, expiration)
+    }
+
+    /// Create `FlushQ` request, quiet version of [Request::flush](#method.flush).
+    pub fn flush_q<T: Expiration>(expiration: T) -> Result<Request, Error> {
+        Request::flushing(Command::FlushQ, expiration)
+    }
+
+    /// Create `SetVBucket` request, changing state of the `vbucket_id` vBucket.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use memcache_proto::{Request, VBucketState};
+    ///
+    /// let request = Request::set_vbucket(42, VBucketState::Active).unwrap();
+    /// ```
+    pub fn set_vbucket(vbucket_id: u16, state: VBucketState) -> Result<Request, Error> {
+        let mut request = Request::new(Command::SetVBucket);
+        request.set_vbucket_id(vbucket_id);
+        request.set_extras(Some(extras::SetVBucket::new(state)))?;
+        Ok(request)
+    }
+
+    /// Create `GetVBucket` request, see [Response::vbucket_state](struct.Response.html#method.vbucket_state).
+    pub fn get_vbucket(vbucket_id: u16) -> Request {
+        let mut request = Request::new(Command::GetVBucket);
+        request.set_vbucket_id(vbucket_id);
+        request
+    }
+
+    /// Create `DelVBucket` request.
+    pub fn del_vbucket(vbucket_id: u16) -> Request {
+        let mut request = Request::new(Command::DelVBucket);
+        request.set_vbucket_id(vbucket_id);
+        request
+    }
+
+    /// Create `TapConnect` request.
+    ///
+    /// Optional `name` identifies the TAP stream on the server,
+    /// request body is filled from the `extras` backfill date and vBuckets list.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use memcache_proto::Request;
+    /// use memcache_proto::extras::{TapConnect, TapFlags};
+    ///
+    /// let extras = TapConnect::build()
+    ///     .flags(TapFlags::DUMP)
+    ///     .vbuckets(vec![0, 1])
+    ///     .finish();
+    /// let request = Request::tap_connect(Some("node-1"), extras).unwrap();
+    /// ```
+    pub fn tap_connect<K: AsRef<[u8]>>(name: Option<K>, extras: extras::TapConnect) -> Result<Request, Error> {
+        let mut request = Request::new(Command::TapConnect);
+        request.set_key(name);
+        let body = extras.body()?;
+        if !body.is_empty() {
+            request.set_value(Some(body));
+        }
+        request.set_extras(Some(extras))?;
+        Ok(request)
+    }
+
+    /// Create range request for the keys between `start` and `end`.
+    ///
+    /// End key is sent at the beginning of the request value, followed by the optional `value`,
+    /// end key length in `extras` is filled automatically.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `command` is not a range one or if end key is too long.
+    pub fn range<K, E, V>(command: Command, start: K, end: E, value: Option<V>, mut extras: extras::Range)
+            -> Result<Request, Error> where K: AsRef<[u8]>, E: AsRef<[u8]>, V: AsRef<[u8]> {
+        let end = end.as_ref();
+        if end.len() > MAX_KEY_LENGTH {
+            return Err(Error::KeyTooLong(end.len()));
+        }
+        extras.set_end_key_length(end.len() as u16);
+
+        let mut body = Vec::from(end);
+        if let Some(value) = value {
+            body.extend_from_slice(value.as_ref());
+        }
+
+        let mut request = Request::keyed(command, start);
+        request.set_extras(Some(extras))?;
+        request.set_value(Some(body));
+        Ok(request)
+    }
+
+    /// Create `RGet` request, see [Request::range](#method.range).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use memcache_proto::{Request, extras};
+    ///
+    /// let request = Request::rget(b"a", b"z", extras::Range::new(100)).unwrap();
+    /// ```
+    pub fn rget<K, E>(start: K, end: E, extras: extras::Range) -> Result<Request, Error>
+            where K: AsRef<[u8]>, E: AsRef<[u8]> {
+        Request::range::<K, E, &[u8]>(Command::RGet, start, end, None, extras)
+    }
+
+    /// Create `RSet` request, storing the same `value` for all the range keys.
+    pub fn rset<K, E, V>(start: K, end: E, value: V, extras: extras::Range) -> Result<Request, Error>
+            where K: AsRef<[u8]>, E: AsRef<[u8]>, V: AsRef<[u8]> {
+        Request::range(Command::RSet, start, end, Some(value), extras)
+    }
+
+    /// Create `RSetQ` request, quiet version of [Request::rset](#method.rset).
+    pub fn rset_q<K, E, V>(start: K, end: E, value: V, extras: extras::Range) -> Result<Request, Error>
+            where K: AsRef<[u8]>, E: AsRef<[u8]>, V: AsRef<[u8]> {
+        Request::range(Command::RSetQ, start, end, Some(value), extras)
+    }
+
+    /// Create `RDelete` request.
+    pub fn rdelete<K, E>(start: K, end: E, extras: extras::Range) -> Result<Request, Error>
+            where K: AsRef<[u8]>, E: AsRef<[u8]> {
+        Request::range::<K, E, &[u8]>(Command::RDelete, start, end, None, extras)
+    }
+
+    /// Create `RDeleteQ` request, quiet version of [Request::rdelete](#method.rdelete).
+    pub fn rdelete_q<K, E>(start: K, end: E, extras: extras::Range) -> Result<Request, Error>
+            where K: AsRef<[u8]>, E: AsRef<[u8]> {
+        Request::range::<K, E, &[u8]>(Command::RDeleteQ, start, end, None, extras)
+    }
+
+    /// Create `Stat` request.
+    ///
+    /// Optional `group` key can be used to fetch specific statistics group,
+    /// see [StatGroup](enum.StatGroup.html) for the well-known ones.
+    pub fn stat<K: AsRef<[u8]>>(group: Option<K>) -> Request {
+        let mut request = Request::new(Command::Stat);
+        request.set_key(group);
+        request
+    }
+
+    /// Create `Version` request.
+    pub fn version() -> Request {
+        Request::new(Command::Version)
+    }
+
+    /// Create `Noop` request.
+    pub fn noop() -> Request {
+        Request::new(Command::Noop)
+    }
+
+    /// Create `Quit` request.
+    pub fn quit() -> Request {
+        Request::new(Command::Quit)
+    }
+
+    /// Create `QuitQ` request, quiet version of [Request::quit](#method.quit).
+    pub fn quit_q() -> Request {
+        Request::new(Command::QuitQ)
+    }
+
+    fn keyed<K: AsRef<[u8]>>(command: Command, key: K) -> Request {
+        let mut request = Request::new(command);
+        request.set_key(Some(key));
+        request
+    }
+
+    fn keyed_value<K: AsRef<[u8]>, V: AsRef<[u8]>>(command: Command, key: K, value: V) -> Request {
+        let mut request = Request::keyed(command, key);
+        request.set_value(Some(value));
+        request
+    }
+
+    fn storage<K, V>(command: Command, key: K, value: V, extras: extras::Set) -> Result<Request, Error>
+            where K: AsRef<[u8]>, V: AsRef<[u8]> {
+        let mut request = Request::keyed_value(command, key, value);
+        request.set_extras(Some(extras))?;
+        Ok(request)
+    }
+
+    fn counter<K: AsRef<[u8]>>(command: Command, key: K, extras: extras::Increment) -> Result<Request, Error> {
+        let mut request = Request::keyed(command, key);
+        request.set_extras(Some(extras))?;
+        Ok(request)
+    }
+
+    fn touching<K: AsRef<[u8]>, T: Expiration>(command: Command, key: K, expiration: T) -> Result<Request, Error> {
+        let mut request = Request::keyed(command, key);
+        request.set_extras(Some(extras::Touch::new(expiration)))?;
+        Ok(request)
+    }
+
+    fn flushing<T: Expiration>(command: Command, expiration: T) -> Result<Request, Error> {
+        let mut request = Request::new(command);
+        request.set_extras(Some(extras::Flush::new(Some(expiration))))?;
+        Ok(request)
+    }
+}
@@ -4,14 +4,30 @@ use std::convert::Into;
 use std::default::Default;
 
 use bytes::BufMut;
-use byteorder::{NetworkEndian};
+use enum_primitive::FromPrimitive;
+use byteorder::{ByteOrder, NetworkEndian, ReadBytesExt};
 
-use super::{Magic, Command, DataType};
+use super::{Magic, Command, DataType, Error};
+use super::extras::{Extras, Expiry, Clock};
+use super::framing::{self, HEADER_SIZE};
 pub use self::builder::RequestBuilder;
+pub use self::borrowed::RequestRef;
+pub use self::vectored::VectoredRequest;
 
 mod builder;
+mod borrowed;
+mod vectored;
+mod constructors;
 
-const HEADER_SIZE: usize = 24;
+/// Maximum key length accepted by `memcached`.
+///
+/// Reference: https://github.com/memcached/memcached/wiki/BinaryProtocolRevamped#keys
+pub const MAX_KEY_LENGTH: usize = 250;
+
+/// Default limit of the request body length, 1 MiB.
+///
+/// It matches the default `memcached` item size limit.
+pub const DEFAULT_MAX_BODY_LENGTH: usize = 1024 * 1024;
 
 /// Memcached request instance.
 pub struct Request {
@@ -26,6 +42,9 @@ pub struct Request {
     extras: Option<Vec<u8>>,
     key: Option<Vec<u8>>,
     value: Option<Vec<u8>>,
+
+    // Unresolved expiration from the extras, converted when request is encoded
+    expiration: Option<Expiry>,
 }
 
 impl Request {
@@ -42,7 +61,7 @@ impl Request {
     pub fn new(command: Command) -> Request {
         Request {
             magic: Magic::Request,
-            opcode: command,
+            opcode: command.normalize(),
             data_type: DataType::RawBytes,
             vbucket_id: 0x00,
             opaque: 0,
@@ -50,6 +69,7 @@ impl Request {
             extras: None,
             key: None,
             value: None,
+            expiration: None,
         }
     }
 
@@ -58,6 +78,124 @@ impl Request {
         RequestBuilder::new(command)
     }
 
+    pub fn magic(&self) -> &Magic {
+        &self.magic
+    }
+
+    pub fn command(&self) -> &Command {
+        &self.opcode
+    }
+
+    pub fn data_type(&self) -> &DataType {
+        &self.data_type
+    }
+
+    pub fn vbucket_id(&self) -> &u16 {
+        &self.vbucket_id
+    }
+
+    pub fn opaque(&self) -> &u32 {
+        &self.opaque
+    }
+
+    pub fn cas(&self) -> &u64 {
+        &self.cas
+    }
+
+    pub fn extras(&self) -> Option<&[u8]> {
+        self.extras.as_ref().map(|extras| extras.as_slice())
+    }
+
+    pub fn key(&self) -> Option<&[u8]> {
+        self.key.as_ref().map(|key| key.as_slice())
+    }
+
+    pub fn value(&self) -> Option<&[u8]> {
+        self.value.as_ref().map(|value| value.as_slice())
+    }
+
+    /// Attempt to read a request from the `src`.
+    ///
+    /// Mirrors [Response::read](struct.Response.html#method.read),
+    /// available return values:
+    ///
+    ///   * `Ok(None)` - there is not enough data to read full request
+    ///   * `Ok(Some(Request))` - read was successful
+    ///   * `Err(..)` - invalid request was read
+    ///
+    /// Only the first packet is read from `src`,
+    /// `Request::len()` method can be used to find out how many bytes were consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use memcache_proto::{Request, Command};
+    ///
+    /// let mut buf: Vec<u8> = vec![];
+    /// Request::get(b"Hello").write(&mut buf).unwrap();
+    ///
+    /// let request = Request::read(&buf).unwrap().unwrap();
+    /// assert_eq!(*request.command(), Command::Get);
+    /// assert_eq!(request.key(), Some(&b"Hello"[..]));
+    /// ```
+    pub fn read(src: &[u8]) -> Result<Option<Request>, Error> {
+        let length = src.len();
+        if length < HEADER_SIZE {
+            return Ok(None);
+        }
+
+        let body_length = NetworkEndian::read_u32(&src[8..12]) as usize;
+        if length < HEADER_SIZE + body_length {  // Body is not received yet
+            return Ok(None);
+        }
+
+        Request::from_parts(&src[..HEADER_SIZE], &src[HEADER_SIZE..HEADER_SIZE + body_length]).map(Some)
+    }
+
+    // Parse request from the complete `header` and `body` of the packet
+    fn from_parts(header: &[u8], body: &[u8]) -> Result<Request, Error> {
+        let body_length = body.len();
+        let mut cursor = io::Cursor::new(header);
+
+        let magic = cursor.read_u8()?;
+        let magic = Magic::from_u8(magic).ok_or_else(|| Error::UnknownMagic(magic))?;
+        if magic != Magic::Request {
+            return Err(Error::UnexpectedMagic(magic));
+        }
+        let opcode = cursor.read_u8()?;
+        let opcode = Command::from(opcode);
+        let key_length = cursor.read_u16::<NetworkEndian>()? as usize;
+        let extras_length = cursor.read_u8()? as usize;
+        let data_type = cursor.read_u8()?;
+        let data_type = DataType::from_u8(data_type).ok_or_else(|| Error::UnknownDataType(data_type))?;
+        let vbucket_id = cursor.read_u16::<NetworkEndian>()?;
+        cursor.set_position(12);
+        let opaque = cursor.read_u32::<NetworkEndian>()?;
+        let cas = cursor.read_u64::<NetworkEndian>()?;
+
+        if extras_length + key_length > body_length {
+            return Err(Error::InvalidBodyLength(body_length));
+        }
+
+        Request::validate(opcode, extras_length, key_length, body_length - extras_length - key_length)?;
+
+        let (extras, rest) = body.split_at(extras_length);
+        let (key, value) = rest.split_at(key_length);
+
+        Ok(Request {
+            magic: magic,
+            opcode: opcode,
+            data_type: data_type,
+            vbucket_id: vbucket_id,
+            opaque: opaque,
+            cas: cas,
+            extras: Request::section(extras),
+            key: Request::section(key),
+            value: Request::section(value),
+            expiration: None,
+        })
+    }
+
     /// Provide key field.
     ///
     /// # Examples
@@ -93,13 +231,64 @@ impl Request {
         };
     }
 
-// TODO:
-//    pub fn set_extras<T: AsRef<[u8]>>(&mut self, extras: Option<T>) {
-//        self.extras = match extras {
-//            Some(ref extras) => Some(Vec::from(extras.as_ref())),
-//            None => None,
-//        };
-//    }
+    /// Provide extras field.
+    ///
+    /// Extras are serialized immediately via [Extras::write](extras/trait.Extras.html#method.write),
+    /// so invalid values are reported by this call. Expiration is kept unresolved
+    /// and relative expirations longer than 30 days are converted into unix timestamps
+    /// when the request is encoded, see [Request::write_with](#method.write_with).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use memcache_proto::{Request, Command, extras};
+    ///
+    /// let mut request = Request::new(Command::Set);
+    /// request.set_extras(Some(extras::Set::new(0xdeadbeef, 3600_u32))).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [Error::InvalidExtras](enum.Error.html#variant.InvalidExtras)
+    /// if extras type can't be used with the request command.
+    pub fn set_extras<E: Extras>(&mut self, extras: Option<E>) -> Result<(), Error> {
+        let (raw, expiration) = match extras {
+            Some(ref extras) => {
+                if !E::request_commands().contains(&self.opcode) {
+                    return Err(Error::InvalidExtras(self.opcode));
+                }
+                let mut buf: Vec<u8> = Vec::new();
+                extras.write(&mut buf)?;
+                // Some extras, ex. `extras::Flush`, might be encoded as an empty section
+                if buf.is_empty() { (None, None) } else { (Some(buf), extras.expiry()) }
+            },
+            None => (None, None),
+        };
+        self.extras = raw;
+        self.expiration = expiration;
+
+        Ok(())
+    }
+
+    /// Convert request into its quiet version.
+    ///
+    /// Request is returned unchanged if command has no quiet version,
+    /// see [Command::quiet](enum.Command.html#method.quiet).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use memcache_proto::{Request, Command};
+    ///
+    /// let request = Request::get_k(b"Hello").into_quiet();
+    /// assert_eq!(*request.command(), Command::GetKQ);
+    /// ```
+    pub fn into_quiet(mut self) -> Request {
+        if let Some(command) = self.opcode.quiet() {
+            self.opcode = command;
+        }
+        self
+    }
 
     /// Provide virtual bucket ID field.
     ///
@@ -151,6 +340,9 @@ impl Request {
 
     /// Write serialized request as a bytes into `T`
     ///
+    /// All lengths are validated before anything is written,
+    /// so `out` is never left with a malformed frame.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -164,65 +356,190 @@ impl Request {
     ///
     /// # Errors
     ///
-    /// Returns an [`std::io::Error`][Error] if write had failed somehow.
-    ///
-    /// [Error]: ../../std/io/struct.Error.html
-    pub fn write<T: BufMut>(&self, out: &mut T) -> io::Result<()> {
-        // TODO: Handle capacity problems in `out`
-        out.put_u8(self.magic as u8);
-        out.put_u8(self.opcode as u8);
-        let key_length = match self.key {
-            // TODO: Possible len truncation
-            Some(ref key) => key.len() as u16,
-            None => 0,
-        };
-        out.put_u16::<NetworkEndian>(key_length);
-        let extras_length = match self.extras {
-            // TODO: Possible len truncation
-            Some(ref extras) => extras.len() as u8,
-            None => 0,
-        };
-        out.put_u8(extras_length);
-        out.put_u8(self.data_type as u8);
-        out.put_u16::<NetworkEndian>(self.vbucket_id);
-        let body_length: u32 = match self.value {
-            Some(ref value) => value.len() as u32,
-            None => 0,
-        } + key_length as u32 + extras_length as u32;
-        out.put_u32::<NetworkEndian>(body_length);
-        out.put_u32::<NetworkEndian>(self.opaque);
-        out.put_u64::<NetworkEndian>(self.cas);
-
-        if let Some(ref extras) = self.extras {
-            out.put_slice(extras);
-        }
+    /// Returns an [Error](enum.Error.html) if key is longer than
+    /// [MAX_KEY_LENGTH](constant.MAX_KEY_LENGTH.html) bytes, extras are longer than 255 bytes,
+    /// total body length does not fit into `u32` or `out` has not enough remaining capacity.
+    ///
+    /// Request is also checked against the command requirements, ex. `Delete` must have a key
+    /// and must not have a value, see [Command](enum.Command.html) methods.
+    pub fn write<T: BufMut>(&self, out: &mut T) -> Result<(), Error> {
+        RequestRef::from(self).write(out)
+    }
 
-        if let Some(ref key) = self.key {
-            out.put_slice(key);
-        }
+    /// Write serialized request as a bytes into `T`, relative expirations
+    /// longer than 30 days are converted with a custom `clock`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::{SystemTime, Duration, UNIX_EPOCH};
+    /// use memcache_proto::{Request, extras};
+    /// use memcache_proto::extras::Clock;
+    ///
+    /// struct Fixed;
+    ///
+    /// impl Clock for Fixed {
+    ///     fn now(&self) -> SystemTime {
+    ///         UNIX_EPOCH + Duration::from_secs(1_500_000_000)
+    ///     }
+    /// }
+    ///
+    /// let expiration = Duration::from_secs(60 * 60 * 24 * 60);
+    /// let request = Request::set(b"Hello", b"World", extras::Set::new(0, expiration)).unwrap();
+    /// let mut buf: Vec<u8> = Vec::with_capacity(request.len());
+    /// request.write_with(&mut buf, &Fixed).unwrap();
+    /// assert_eq!(&buf[28..32], &[0x59, 0xb7, 0x49, 0x00]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as for [Request::write](#method.write), `Error::InvalidExpiration`
+    /// is returned if expiration can't be converted with the `clock`.
+    pub fn write_with<T: BufMut, C: Clock>(&self, out: &mut T, clock: &C) -> Result<(), Error> {
+        RequestRef::from(self).write_with(out, clock)
+    }
+
+    /// Write serialized request into the blocking `std::io` writer.
+    ///
+    /// Request is serialized into an intermediate buffer first,
+    /// so `out` receives the whole packet with a single `write_all` call.
+    /// Use [Request::encode_vectored](#method.encode_vectored) to avoid the copy of large values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use memcache_proto::Request;
+    ///
+    /// let mut out: Vec<u8> = vec![];
+    /// Request::get(b"Hello").write_to(&mut out).unwrap();
+    /// assert_eq!(out.len(), 29);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as for [Request::write](#method.write), I/O errors are returned as `Error::Io`.
+    pub fn write_to<W: io::Write>(&self, out: &mut W) -> Result<(), Error> {
+        RequestRef::from(self).write_to(out)
+    }
+
+    /// Serialize request header for the vectored (scatter/gather) writes.
+    ///
+    /// Extras, key and value are borrowed from the request instead of being copied
+    /// after the header, see [VectoredRequest](struct.VectoredRequest.html).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use memcache_proto::Request;
+    ///
+    /// let request = Request::append(b"Hello", &vec![0x61; 300 * 1024][..]);
+    /// let encoded = request.encode_vectored().unwrap();
+    /// assert_eq!(encoded.header().len(), 24);
+    /// assert_eq!(encoded.value().unwrap().as_ptr(), request.value().unwrap().as_ptr());
+    ///
+    /// let mut out: Vec<u8> = vec![];
+    /// encoded.write_to(&mut out).unwrap();
+    /// assert_eq!(out.len(), request.len());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as for [Request::write](#method.write), except `Error::NotEnoughCapacity`.
+    pub fn encode_vectored(&self) -> Result<VectoredRequest<'_>, Error> {
+        RequestRef::from(self).encode_vectored()
+    }
+
+    /// Serialize request header for the vectored writes, relative expirations
+    /// longer than 30 days are converted with a custom `clock`,
+    /// see [Request::write_with](#method.write_with).
+    pub fn encode_vectored_with<C: Clock>(&self, clock: &C) -> Result<VectoredRequest<'_>, Error> {
+        RequestRef::from(self).encode_vectored_with(clock)
+    }
+
+    /// Read a single request from the blocking `std::io` reader.
+    ///
+    /// Reads the 24-byte header first and then exactly the body length bytes,
+    /// nothing past the packet is consumed from `src`.
+    /// Requests with a body longer than [DEFAULT_MAX_BODY_LENGTH](constant.DEFAULT_MAX_BODY_LENGTH.html)
+    /// are rejected, see [Request::read_from_with_limit](#method.read_from_with_limit).
+    ///
+    /// `Ok(None)` is returned if `src` is at EOF before the first header byte,
+    /// ex. client closed connection between the requests.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::io;
+    /// use memcache_proto::{Request, Command};
+    ///
+    /// let mut buf: Vec<u8> = vec![];
+    /// Request::noop().write_to(&mut buf).unwrap();
+    ///
+    /// let mut src = io::Cursor::new(buf);
+    /// let request = Request::read_from(&mut src).unwrap().unwrap();
+    /// assert_eq!(*request.command(), Command::Noop);
+    /// assert!(Request::read_from(&mut src).unwrap().is_none());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails with an `io::ErrorKind::UnexpectedEof` I/O error if `src` ends in the middle
+    /// of a packet, otherwise errors are the same as for [Request::read](#method.read).
+    pub fn read_from<R: io::Read>(src: &mut R) -> Result<Option<Request>, Error> {
+        Request::read_from_with_limit(src, DEFAULT_MAX_BODY_LENGTH)
+    }
 
-        if let Some(ref value) = self.value {
-            out.put_slice(value);
+    /// Read a single request from the blocking `std::io` reader,
+    /// rejecting bodies longer than `max_body_length` bytes.
+    ///
+    /// Header magic and body length are checked before the body is read,
+    /// so a garbage header does not make it wait for gigabytes of data.
+    ///
+    /// # Errors
+    ///
+    /// Same as for [Request::read_from](#method.read_from), `Error::BodyLimitExceeded`
+    /// is returned if body length exceeds the limit.
+    pub fn read_from_with_limit<R: io::Read>(src: &mut R, max_body_length: usize)
+            -> Result<Option<Request>, Error> {
+        let mut header = [0; HEADER_SIZE];
+        if !framing::read_header(src, &mut header)? {
+            return Ok(None);
         }
 
-        Ok(())
+        let body_length = framing::check_request_header(&header, max_body_length)?;
+        let body = framing::read_body(src, HEADER_SIZE, body_length)?;
+
+        Request::from_parts(&header, &body).map(Some)
     }
 
     pub fn len(&self) -> usize {
-        let mut result: usize = HEADER_SIZE;
-        if let Some(ref extras) = self.extras {
-            result += extras.len();
-        }
+        RequestRef::from(self).len()
+    }
 
-        if let Some(ref key) = self.key {
-            result += key.len();
+    /// Check packet parts against the command requirements.
+    fn validate(command: Command, extras_length: usize, key_length: usize, value_length: usize)
+            -> Result<(), Error> {
+        if command.requires_key() && key_length == 0 {
+            return Err(Error::MissingKey(command));
         }
-
-        if let Some(ref value) = self.value {
-            result += value.len();
+        if !command.allows_value() && value_length > 0 {
+            return Err(Error::UnexpectedValue(command));
+        }
+        match command.request_extras_len() {
+            Some(expected) if expected != extras_length => Err(Error::InvalidLength {
+                expected: expected,
+                actual: extras_length,
+            }),
+            _ => Ok(()),
         }
+    }
 
-        result
+    fn section(raw: &[u8]) -> Option<Vec<u8>> {
+        if raw.is_empty() {
+            None
+        } else {
+            Some(Vec::from(raw))
+        }
     }
 }
 
@@ -237,6 +554,7 @@ impl fmt::Debug for Request {
             .field("key", &self.key)
             .field("value", &self.value)
             .field("extras", &self.extras)
+            .field("expiration", &self.expiration)
             .finish()
     }
 }
@@ -253,6 +571,7 @@ impl Default for Request {
             extras: None,
             key: None,
             value: None,
+            expiration: None,
         }
     }
 }
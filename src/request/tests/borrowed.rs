@@ -0,0 +1,72 @@
+use ::{Request, RequestRef, Error, Command, MAX_KEY_LENGTH};
+use extras;
+
+#[test]
+fn test_same_as_owned() {
+    let owned = Request::set(b"Hello", b"World", extras::Set::new(0xdeadbeef, 3600_u32)).unwrap();
+
+    let extras = owned.extras().unwrap().to_vec();
+    let mut borrowed = RequestRef::new(Command::Set);
+    borrowed.set_key(Some("Hello"));
+    borrowed.set_value(Some(b"World"));
+    borrowed.set_raw_extras(Some(&extras));
+
+    let mut expected: Vec<u8> = vec![];
+    owned.write(&mut expected).unwrap();
+    let mut result: Vec<u8> = vec![];
+    borrowed.write(&mut result).unwrap();
+
+    assert_eq!(result, expected);
+    assert_eq!(borrowed.len(), expected.len());
+}
+
+#[test]
+fn test_from_request() {
+    let mut owned = Request::append(b"Hello", b"World");
+    owned.set_opaque(0xcafe_u32);
+    owned.set_cas(5u64);
+
+    let mut expected: Vec<u8> = vec![];
+    owned.write(&mut expected).unwrap();
+    let mut result: Vec<u8> = vec![];
+    RequestRef::from(&owned).write(&mut result).unwrap();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn test_large_value() {
+    let value = vec![0x61; 512 * 1024];
+    let mut request = RequestRef::new(Command::Append);
+    request.set_key(Some("Hello"));
+    request.set_value(Some(&value));
+
+    let mut result: Vec<u8> = vec![];
+    request.write(&mut result).unwrap();
+
+    assert_eq!(result.len(), 24 + 5 + value.len());
+    assert_eq!(&result[8..12], &[0x00, 0x08, 0x00, 0x05]);
+    assert_eq!(&result[29..], &value[..]);
+}
+
+#[test]
+fn test_validation() {
+    let key = vec![0x61; MAX_KEY_LENGTH + 1];
+    let mut request = RequestRef::new(Command::Get);
+    request.set_key(Some(&key));
+
+    let mut result: Vec<u8> = vec![];
+    match request.write(&mut result) {
+        Err(Error::KeyTooLong(_)) => {},
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    let extras = vec![0x00; 256];
+    let mut request = RequestRef::new(Command::Set);
+    request.set_raw_extras(Some(&extras));
+    match request.write(&mut result) {
+        Err(Error::ExtrasTooLong(256)) => {},
+        other => panic!("Unexpected result: {:?}", other),
+    }
+    assert!(result.is_empty());
+}
@@ -0,0 +1,146 @@
+use ::{Request, Command, VBucketState};
+use extras;
+
+fn serialize(request: &Request) -> Vec<u8> {
+    let mut result: Vec<u8> = vec![];
+    request.write(&mut result).unwrap();
+    result
+}
+
+#[test]
+fn test_get() {
+    let mut expected = Request::new(Command::Get);
+    expected.set_key(Some(b"Hello"));
+
+    assert_eq!(serialize(&Request::get(b"Hello")), serialize(&expected));
+}
+
+#[test]
+fn test_quiet_get_variants() {
+    assert_eq!(serialize(&Request::get_q("Hello"))[1], 0x09);
+    assert_eq!(serialize(&Request::get_k("Hello"))[1], 0x0c);
+    assert_eq!(serialize(&Request::get_kq("Hello"))[1], 0x0d);
+}
+
+#[test]
+fn test_set() {
+    let request = Request::set(b"Hello", b"World", extras::Set::new(0xdeadbeef, 3600_u32)).unwrap();
+
+    let expected: Vec<u8> = vec![
+        0x80, 0x01, 0x00, 0x05,
+        0x08, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x12,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0xde, 0xad, 0xbe, 0xef,
+        0x00, 0x00, 0x0e, 0x10,
+        0x48, 0x65, 0x6c, 0x6c,
+        0x6f, 0x57, 0x6f, 0x72,
+        0x6c, 0x64,
+    ];
+
+    assert_eq!(serialize(&request), expected);
+}
+
+#[test]
+fn test_add_q() {
+    let request = Request::add_q(b"Hello", b"World", extras::Add::default()).unwrap();
+    let result = serialize(&request);
+
+    assert_eq!(result[1], 0x12);
+    assert_eq!(result[4], 0x08);
+    assert_eq!(&result[32..], b"HelloWorld");
+}
+
+#[test]
+fn test_append() {
+    let result = serialize(&Request::append(b"Hello", b"World"));
+
+    assert_eq!(result[1], 0x0e);
+    assert_eq!(result[4], 0x00);
+    assert_eq!(&result[24..], b"HelloWorld");
+}
+
+#[test]
+fn test_delete() {
+    let result = serialize(&Request::delete(b"Hello"));
+
+    assert_eq!(result[1], 0x04);
+    assert_eq!(&result[24..], b"Hello");
+}
+
+#[test]
+fn test_decrement() {
+    let extras = extras::Decrement::build().amount(5).finish();
+    let result = serialize(&Request::decrement(b"counter", extras).unwrap());
+
+    assert_eq!(result[1], 0x06);
+    assert_eq!(result[4], 20);
+    assert_eq!(&result[24..32], &[0, 0, 0, 0, 0, 0, 0, 5]);
+    assert_eq!(&result[44..], b"counter");
+}
+
+#[test]
+fn test_touch() {
+    let expected: Vec<u8> = vec![
+        0x80, 0x1c, 0x00, 0x05,
+        0x04, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x09,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x0e, 0x10,
+        0x48, 0x65, 0x6c, 0x6c,
+        0x6f,
+    ];
+
+    assert_eq!(serialize(&Request::touch(b"Hello", 3600_u32).unwrap()), expected);
+}
+
+#[test]
+fn test_flush() {
+    let result = serialize(&Request::flush(60_u32).unwrap());
+
+    assert_eq!(result[1], 0x08);
+    assert_eq!(result[4], 0x04);
+    assert_eq!(&result[24..], &[0x00, 0x00, 0x00, 0x3c]);
+}
+
+#[test]
+fn test_stat() {
+    assert_eq!(serialize(&Request::stat::<&[u8]>(None)).len(), 24);
+    assert_eq!(&serialize(&Request::stat(Some("items")))[24..], b"items");
+}
+
+#[test]
+fn test_blank_requests() {
+    assert_eq!(serialize(&Request::version()), serialize(&Request::new(Command::Version)));
+    assert_eq!(serialize(&Request::noop()), serialize(&Request::new(Command::Noop)));
+    assert_eq!(serialize(&Request::quit()), serialize(&Request::new(Command::Quit)));
+    assert_eq!(serialize(&Request::quit_q()), serialize(&Request::new(Command::QuitQ)));
+}
+
+#[test]
+fn test_touch_extras() {
+    let mut expected = Request::new(Command::Gat);
+    expected.set_key(Some(b"Hello"));
+    expected.set_extras(Some(extras::Gat::new(3600_u32))).unwrap();
+
+    assert_eq!(serialize(&Request::gat(b"Hello", 3600_u32).unwrap()), serialize(&expected));
+}
+
+#[test]
+fn test_vbucket_requests() {
+    let mut expected = Request::new(Command::SetVBucket);
+    expected.set_vbucket_id(7_u16);
+    expected.set_extras(Some(extras::SetVBucket::new(VBucketState::Active))).unwrap();
+    assert_eq!(serialize(&Request::set_vbucket(7, VBucketState::Active).unwrap()), serialize(&expected));
+
+    let result = serialize(&Request::get_vbucket(7));
+    assert_eq!(result[1], 0x3e);
+    assert_eq!(&result[6..8], &[0x00, 0x07]);
+    assert_eq!(result.len(), 24);
+
+    assert_eq!(serialize(&Request::del_vbucket(7))[1], 0x3f);
+}
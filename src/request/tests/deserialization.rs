@@ -0,0 +1,125 @@
+use ::{Request, Command, DataType, Magic, Error};
+use extras;
+
+#[test]
+fn test_get() {
+    let buf: Vec<u8> = vec![
+        0x80, 0x00, 0x00, 0x05,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x05,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x48, 0x65, 0x6c, 0x6c,
+        0x6f,
+    ];
+
+    let request = Request::read(&buf).unwrap().unwrap();
+
+    assert_eq!(*request.magic(), Magic::Request);
+    assert_eq!(*request.command(), Command::Get);
+    assert_eq!(*request.data_type(), DataType::RawBytes);
+    assert!(request.extras().is_none());
+    assert_eq!(request.key().unwrap(), b"Hello");
+    assert!(request.value().is_none());
+    assert_eq!(request.len(), buf.len());
+}
+
+#[test]
+fn test_set() {
+    let mut original = Request::set(b"Hello", b"World", extras::Set::new(0xdeadbeef, 3600_u32)).unwrap();
+    original.set_vbucket_id(3u16);
+    original.set_opaque(0xcafe_u32);
+    original.set_cas(42u64);
+    let mut buf: Vec<u8> = vec![];
+    original.write(&mut buf).unwrap();
+
+    let request = Request::read(&buf).unwrap().unwrap();
+
+    assert_eq!(*request.command(), Command::Set);
+    assert_eq!(*request.vbucket_id(), 3);
+    assert_eq!(*request.opaque(), 0xcafe);
+    assert_eq!(*request.cas(), 42);
+    assert_eq!(request.extras().unwrap(), [0xde, 0xad, 0xbe, 0xef, 0x00, 0x00, 0x0e, 0x10]);
+    assert_eq!(request.key().unwrap(), b"Hello");
+    assert_eq!(request.value().unwrap(), b"World");
+}
+
+#[test]
+fn test_partial() {
+    let mut buf: Vec<u8> = vec![];
+    Request::append(b"Hello", b"World").write(&mut buf).unwrap();
+
+    for length in 0..buf.len() {
+        assert!(Request::read(&buf[..length]).unwrap().is_none());
+    }
+    assert!(Request::read(&buf).unwrap().is_some());
+}
+
+#[test]
+fn test_response_magic() {
+    let mut buf: Vec<u8> = vec![];
+    Request::noop().write(&mut buf).unwrap();
+    buf[0] = 0x81;
+
+    match Request::read(&buf) {
+        Err(Error::UnexpectedMagic(Magic::Response)) => {},
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_body_length() {
+    let buf: Vec<u8> = vec![
+        0x80, 0x00, 0x00, 0x05,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x02,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x48, 0x65,
+    ];
+
+    match Request::read(&buf) {
+        Err(Error::InvalidBodyLength(2)) => {},
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_command_requirements() {
+    let buf: Vec<u8> = vec![
+        0x80, 0x04, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+    ];
+
+    match Request::read(&buf) {
+        Err(Error::MissingKey(Command::Delete)) => {},
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_unknown_command() {
+    let buf: Vec<u8> = vec![
+        0x80, 0x1f, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x03,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x01, 0x02, 0x03,
+    ];
+
+    let request = Request::read(&buf).unwrap().unwrap();
+    assert_eq!(*request.command(), Command::Unknown(0x1f));
+    assert_eq!(request.value(), Some(&[0x01, 0x02, 0x03][..]));
+
+    let mut raw: Vec<u8> = vec![];
+    request.write(&mut raw).unwrap();
+    assert_eq!(raw, buf);
+}
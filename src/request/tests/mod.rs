@@ -1 +1,5 @@
 mod serialization;
+mod constructors;
+mod deserialization;
+mod borrowed;
+mod vectored;
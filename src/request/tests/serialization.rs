@@ -1,4 +1,7 @@
-use ::{Request, Command};
+use std::io;
+
+use ::{Request, Command, Error, MAX_KEY_LENGTH};
+use extras;
 
 #[test]
 fn test_get() {
@@ -100,3 +103,144 @@ fn test_version() {
     assert_eq!(result, expected);
     assert_eq!(request.len(), expected.len());
 }
+
+#[test]
+fn test_set_with_extras() {
+    let mut request = Request::new(Command::Set);
+    request.set_key(Some(b"Hello"));
+    request.set_value(Some(b"World"));
+    request.set_extras(Some(extras::Set::new(0xdeadbeef, 3600_u32))).unwrap();
+
+    let expected: Vec<u8> = vec![
+        0x80, 0x01, 0x00, 0x05,
+        0x08, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x12,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0xde, 0xad, 0xbe, 0xef,
+        0x00, 0x00, 0x0e, 0x10,
+        0x48, 0x65, 0x6c, 0x6c,
+        0x6f, 0x57, 0x6f, 0x72,
+        0x6c, 0x64,
+    ];
+
+    let mut result: Vec<u8> = vec![];
+    request.write(&mut result).unwrap();
+    assert_eq!(result, expected);
+    assert_eq!(request.len(), expected.len());
+}
+
+#[test]
+fn test_builder_increment_extras() {
+    let extras = extras::Increment::build().amount(1).initial(10).finish();
+    let request = Request::build(Command::Increment)
+        .key(Some(b"counter"))
+        .extras(Some(extras)).unwrap()
+        .finish();
+
+    let mut result: Vec<u8> = vec![];
+    request.write(&mut result).unwrap();
+    assert_eq!(result[4], 20);
+    assert_eq!(&result[24..32], &[0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(&result[32..40], &[0, 0, 0, 0, 0, 0, 0, 10]);
+    assert_eq!(&result[44..], b"counter");
+}
+
+#[test]
+fn test_extras_command_mismatch() {
+    let mut request = Request::new(Command::Get);
+    assert!(request.set_extras(Some(extras::Set::default())).is_err());
+    assert!(request.set_extras(Some(extras::Get::default())).is_err());
+
+    let result = Request::build(Command::Delete)
+        .extras(Some(extras::Increment::default()));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_key_too_long() {
+    let mut request = Request::new(Command::Get);
+    request.set_key(Some(vec![0x61; MAX_KEY_LENGTH + 1]));
+
+    let mut result: Vec<u8> = vec![];
+    match request.write(&mut result) {
+        Err(Error::KeyTooLong(length)) => assert_eq!(length, MAX_KEY_LENGTH + 1),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+    assert!(result.is_empty());
+}
+
+#[test]
+fn test_key_u16_overflow() {
+    let mut request = Request::new(Command::Get);
+    request.set_key(Some(vec![0x61; 70 * 1024]));
+
+    let mut result: Vec<u8> = vec![];
+    assert!(request.write(&mut result).is_err());
+    assert!(result.is_empty());
+}
+
+#[test]
+fn test_not_enough_capacity() {
+    let mut request = Request::new(Command::Get);
+    request.set_key(Some(b"Hello"));
+
+    let mut raw = [0u8; 28];
+    let mut out = io::Cursor::new(&mut raw[..]);
+    match request.write(&mut out) {
+        Err(Error::NotEnoughCapacity { required, remaining }) => {
+            assert_eq!(required, 29);
+            assert_eq!(remaining, 28);
+        },
+        other => panic!("Unexpected result: {:?}", other),
+    }
+    assert_eq!(out.position(), 0);
+}
+
+#[test]
+fn test_command_requirements() {
+    let mut result: Vec<u8> = vec![];
+
+    match Request::new(Command::Get).write(&mut result) {
+        Err(Error::MissingKey(Command::Get)) => {},
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    let mut request = Request::delete(b"Hello");
+    request.set_value(Some(b"World"));
+    match request.write(&mut result) {
+        Err(Error::UnexpectedValue(Command::Delete)) => {},
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    let request = Request::build(Command::Set)
+        .key(Some("Hello"))
+        .value(Some("World"))
+        .finish();
+    match request.write(&mut result) {
+        Err(Error::InvalidLength { expected: 8, actual: 0 }) => {},
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    // Assigned opcode is validated as the known command
+    match Request::new(Command::Unknown(0x1c)).write(&mut result) {
+        Err(Error::MissingKey(Command::Touch)) => {},
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    assert!(result.is_empty());
+
+    // Unassigned opcodes are validated loosely
+    Request::new(Command::Unknown(0x1f)).write(&mut result).unwrap();
+    assert_eq!(result.len(), 24);
+}
+
+#[test]
+fn test_into_quiet() {
+    let request = Request::set(b"Hello", b"World", extras::Set::default()).unwrap().into_quiet();
+    assert_eq!(*request.command(), Command::SetQ);
+
+    let request = Request::noop().into_quiet();
+    assert_eq!(*request.command(), Command::Noop);
+}
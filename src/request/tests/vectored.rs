@@ -0,0 +1,115 @@
+use std::io;
+
+use ::{Request, RequestRef, Error, Command, MAX_KEY_LENGTH};
+use extras;
+
+// Accepts at most `limit` bytes per call and fails every other call with `Interrupted`
+struct Trickle {
+    data: Vec<u8>,
+    limit: usize,
+    calls: usize,
+}
+
+impl io::Write for Trickle {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_vectored(&[io::IoSlice::new(buf)])
+    }
+
+    fn write_vectored(&mut self, bufs: &[io::IoSlice]) -> io::Result<usize> {
+        self.calls += 1;
+        if self.calls % 2 == 0 {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"));
+        }
+
+        let mut written = 0;
+        for buf in bufs {
+            let length = ::std::cmp::min(buf.len(), self.limit - written);
+            self.data.extend_from_slice(&buf[..length]);
+            written += length;
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_same_as_write() {
+    let value = vec![0x61; 300 * 1024];
+    let mut request = Request::set(b"Hello", &value, extras::Set::new(0xdeadbeef, 3600_u32)).unwrap();
+    request.set_opaque(0xcafe_u32);
+    request.set_cas(5u64);
+
+    let mut expected: Vec<u8> = vec![];
+    request.write(&mut expected).unwrap();
+
+    let encoded = request.encode_vectored().unwrap();
+    assert_eq!(encoded.header(), &expected[..24]);
+    assert_eq!(encoded.len(), expected.len());
+
+    let slices = encoded.io_slices();
+    assert_eq!(slices.len(), 5);
+    let joined: Vec<u8> = slices.iter().flat_map(|slice| slice.iter().cloned()).collect();
+    assert_eq!(joined, expected);
+
+    let mut result: Vec<u8> = vec![];
+    encoded.write_to(&mut result).unwrap();
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn test_value_is_not_copied() {
+    let value = vec![0x61; 300 * 1024];
+    let mut request = RequestRef::new(Command::Append);
+    request.set_key(Some("Hello"));
+    request.set_value(Some(&value));
+
+    let encoded = request.encode_vectored().unwrap();
+    assert_eq!(encoded.value().unwrap().as_ptr(), value.as_ptr());
+    assert_eq!(encoded.io_slices()[2].as_ptr(), value.as_ptr());
+}
+
+#[test]
+fn test_empty_sections_skipped() {
+    let request = Request::noop();
+    let encoded = request.encode_vectored().unwrap();
+    let slices = encoded.io_slices();
+    assert_eq!(slices.len(), 1);
+    assert_eq!(slices[0].len(), 24);
+}
+
+#[test]
+fn test_partial_writes() {
+    let request = Request::set(b"Hello", b"World", extras::Set::new(0, 0_u32)).unwrap();
+    let mut expected: Vec<u8> = vec![];
+    request.write(&mut expected).unwrap();
+
+    let mut out = Trickle { data: vec![], limit: 7, calls: 0 };
+    request.encode_vectored().unwrap().write_to(&mut out).unwrap();
+    assert_eq!(out.data, expected);
+}
+
+#[test]
+fn test_write_zero() {
+    let mut buf = [0; 10];
+    let mut out = io::Cursor::new(&mut buf[..]);
+    match Request::get(b"Hello").encode_vectored().unwrap().write_to(&mut out) {
+        Err(Error::Io(ref e)) if e.kind() == io::ErrorKind::WriteZero => {},
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_request() {
+    let key = vec![0x61; MAX_KEY_LENGTH + 1];
+    match Request::get(&key).encode_vectored() {
+        Err(Error::KeyTooLong(length)) => assert_eq!(length, MAX_KEY_LENGTH + 1),
+        other => panic!("unexpected result {:?}", other),
+    }
+
+    let mut request = RequestRef::new(Command::Get);
+    request.set_value(Some("World"));
+    assert!(request.encode_vectored().is_err());
+}
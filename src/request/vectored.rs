@@ -0,0 +1,137 @@
+use std::io::{self, IoSlice};
+use std::fmt;
+
+use super::{Error, HEADER_SIZE};
+
+/// Request encoded for the vectored (scatter/gather) writes.
+///
+/// Only the header and the extras expiration are serialized, rest of the extras,
+/// key and value are borrowed from the request, so large values are sent
+/// without being copied into an intermediate buffer.
+///
+/// Created by [Request::encode_vectored](struct.Request.html#method.encode_vectored)
+/// or [RequestRef::encode_vectored](struct.RequestRef.html#method.encode_vectored).
+///
+/// # Examples
+///
+/// ```rust
+/// use memcache_proto::{RequestRef, Command};
+///
+/// let value = vec![0x61; 512 * 1024];
+/// let mut request = RequestRef::new(Command::Append);
+/// request.set_key(Some("Hello"));
+/// request.set_value(Some(&value));
+///
+/// let encoded = request.encode_vectored().unwrap();
+/// assert_eq!(encoded.io_slices().len(), 3);
+///
+/// let mut out: Vec<u8> = vec![];
+/// encoded.write_to(&mut out).unwrap();
+/// assert_eq!(out.len(), request.len());
+/// ```
+#[derive(Clone, Copy)]
+pub struct VectoredRequest<'a> {
+    header: [u8; HEADER_SIZE],
+    extras: Option<&'a [u8]>,
+    expiration: Option<[u8; 4]>,
+    key: Option<&'a [u8]>,
+    value: Option<&'a [u8]>,
+}
+
+impl<'a> VectoredRequest<'a> {
+
+    pub(super) fn new(header: [u8; HEADER_SIZE], extras: Option<&'a [u8]>, expiration: Option<[u8; 4]>,
+                      key: Option<&'a [u8]>, value: Option<&'a [u8]>) -> VectoredRequest<'a> {
+        VectoredRequest {
+            header: header,
+            extras: extras,
+            expiration: expiration,
+            key: key,
+            value: value,
+        }
+    }
+
+    /// Serialized request header.
+    pub fn header(&self) -> &[u8] {
+        &self.header
+    }
+
+    pub fn key(&self) -> Option<&'a [u8]> {
+        self.key
+    }
+
+    pub fn value(&self) -> Option<&'a [u8]> {
+        self.value
+    }
+
+    /// Slices for the `write_vectored` call: header, followed by non-empty extras, key and value.
+    ///
+    /// Extras with an expiration are split into the borrowed head and the serialized expiration.
+    pub fn io_slices(&self) -> Vec<IoSlice<'_>> {
+        self.remaining_slices(0)
+    }
+
+    /// Write the whole request into the blocking `std::io` writer with `write_vectored` calls.
+    ///
+    /// Partial writes are resumed from the first unwritten byte,
+    /// `io::ErrorKind::Interrupted` errors are retried.
+    pub fn write_to<W: io::Write>(&self, out: &mut W) -> Result<(), Error> {
+        let length = self.len();
+        let mut written = 0;
+        while written < length {
+            match out.write_vectored(&self.remaining_slices(written)) {
+                Ok(0) => return Err(Error::Io(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write the whole packet",
+                ))),
+                Ok(n) => written += n,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {},
+                Err(e) => return Err(Error::Io(e)),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Full packet length, including the header.
+    pub fn len(&self) -> usize {
+        self.sections().iter().map(|section| section.len()).sum()
+    }
+
+    fn sections(&self) -> [&[u8]; 5] {
+        [
+            &self.header,
+            self.extras.unwrap_or(&[]),
+            self.expiration.as_ref().map_or(&[], |expiration| &expiration[..]),
+            self.key.unwrap_or(&[]),
+            self.value.unwrap_or(&[]),
+        ]
+    }
+
+    // Slices of the packet, which are following the first `skip` bytes
+    fn remaining_slices(&self, mut skip: usize) -> Vec<IoSlice<'_>> {
+        let mut slices = Vec::with_capacity(5);
+        for &section in self.sections().iter() {
+            if skip >= section.len() {
+                skip -= section.len();
+                continue;
+            }
+            slices.push(IoSlice::new(&section[skip..]));
+            skip = 0;
+        }
+
+        slices
+    }
+}
+
+impl<'a> fmt::Debug for VectoredRequest<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("VectoredRequest")
+            .field("header", &self.header)
+            .field("extras", &self.extras)
+            .field("expiration", &self.expiration)
+            .field("key", &self.key)
+            .field("value", &self.value)
+            .finish()
+    }
+}
@@ -0,0 +1,152 @@
+use std::u8;
+use std::u16;
+use std::u32;
+use std::fmt;
+
+use bytes::Bytes;
+
+use super::{Magic, Command, DataType, Status, Response, Error};
+use ::extras::Extras;
+
+/// Builder, which can be used in order to create a new [Response](struct.Response.html)
+/// via chain calls.
+///
+/// # Examples
+///
+/// ```rust
+/// use memcache_proto::{Response, Command, Status, extras};
+///
+/// let response = Response::build(Command::GetK)
+///     .status(Status::Ok)
+///     .extras(Some(extras::Get::new(0xdeadbeef))).unwrap()
+///     .key(Some("Hello"))
+///     .value(Some("World"))
+///     .cas(1)
+///     .finish()
+///     .unwrap();
+/// ```
+pub struct ResponseBuilder {
+    opcode: Command,
+    status: Status,
+    opaque: u32,
+    cas: u64,
+    extras: Option<Vec<u8>>,
+    key: Option<Vec<u8>>,
+    value: Option<Vec<u8>>,
+}
+
+impl ResponseBuilder {
+    pub fn new(command: Command) -> Self {
+        ResponseBuilder {
+            opcode: command.normalize(),
+            status: Status::Ok,
+            opaque: 0,
+            cas: 0,
+            extras: None,
+            key: None,
+            value: None,
+        }
+    }
+
+    /// Provide status, `Status::Ok` by default
+    pub fn status(mut self, status: Status) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Provide opaque, should be copied from the request
+    pub fn opaque(mut self, opaque: u32) -> Self {
+        self.opaque = opaque;
+        self
+    }
+
+    /// Provide CAS
+    pub fn cas(mut self, cas: u64) -> Self {
+        self.cas = cas;
+        self
+    }
+
+    /// Provide extras
+    ///
+    /// Fails if extras type is not used in responses for the command.
+    pub fn extras<E: Extras>(mut self, extras: Option<E>) -> Result<Self, Error> {
+        self.extras = match extras {
+            Some(ref extras) => {
+                if !E::response_commands().contains(&self.opcode) {
+                    return Err(Error::InvalidExtras(self.opcode));
+                }
+                let mut buf: Vec<u8> = Vec::new();
+                extras.write(&mut buf)?;
+                Some(buf)
+            },
+            None => None,
+        };
+        Ok(self)
+    }
+
+    /// Provide key
+    pub fn key<T: AsRef<[u8]>>(mut self, key: Option<T>) -> Self {
+        self.key = key.map(|key| Vec::from(key.as_ref()));
+        self
+    }
+
+    /// Provide value, for error responses it should contain a text message
+    pub fn value<T: AsRef<[u8]>>(mut self, value: Option<T>) -> Self {
+        self.value = value.map(|value| Vec::from(value.as_ref()));
+        self
+    }
+
+    /// Consume builder and return complete [Response](struct.Response.html)
+    ///
+    /// # Errors
+    ///
+    /// Fails if key, extras or the whole body are not fitting into the packet header fields.
+    pub fn finish(self) -> Result<Response, Error> {
+        let extras = self.extras.unwrap_or_default();
+        let key = self.key.unwrap_or_default();
+        let value = self.value.unwrap_or_default();
+
+        if extras.len() > u8::MAX as usize {
+            return Err(Error::ExtrasTooLong(extras.len()));
+        }
+        if key.len() > u16::MAX as usize {
+            return Err(Error::KeyTooLong(key.len()));
+        }
+        let body_length = extras.len() + key.len() + value.len();
+        if body_length > u32::MAX as usize {
+            return Err(Error::BodyTooLong(body_length));
+        }
+
+        let mut body = Vec::with_capacity(body_length);
+        body.extend_from_slice(&extras);
+        body.extend_from_slice(&key);
+        body.extend_from_slice(&value);
+
+        Ok(Response {
+            magic: Magic::Response,
+            opcode: self.opcode,
+            key_length: key.len() as u16,
+            extras_length: extras.len() as u8,
+            data_type: DataType::RawBytes,
+            status: self.status,
+            body_length: body_length as u32,
+            opaque: self.opaque,
+            cas: self.cas,
+            body: Bytes::from(body),
+        })
+    }
+}
+
+impl fmt::Debug for ResponseBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ResponseBuilder")
+            .field("command", &self.opcode)
+            .field("status", &self.status)
+            .field("opaque", &self.opaque)
+            .field("cas", &self.cas)
+            .field("key", &self.key)
+            .field("value", &self.value)
+            .field("extras", &self.extras)
+            .finish()
+    }
+}
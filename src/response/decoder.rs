@@ -0,0 +1,135 @@
+use std::fmt;
+use std::cmp;
+use std::default::Default;
+
+use bytes::BytesMut;
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{Response, Error};
+use super::header::Header;
+use ::framing::HEADER_SIZE;
+
+/// Incremental decoder for the stream of responses.
+///
+/// Header is parsed only once, as soon as it is received,
+/// after that decoder waits for the whole body.
+/// Consumed bytes are removed from the buffer, so pipelined responses
+/// can be decoded one by one with the repeated `decode` calls.
+///
+/// Response bodies are split off the buffer without copying,
+/// see [Response::value_bytes](struct.Response.html#method.value_bytes).
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate bytes;
+/// extern crate memcache_proto;
+///
+/// use bytes::BytesMut;
+/// use memcache_proto::{Response, ResponseDecoder, Command};
+///
+/// # fn main() {
+///
+/// let mut raw: Vec<u8> = vec![];
+/// Response::build(Command::Noop).finish().unwrap().write(&mut raw).unwrap();
+/// Response::build(Command::Version).value(Some("1.5.0")).finish().unwrap().write(&mut raw).unwrap();
+///
+/// let mut buf = BytesMut::from(raw);
+/// let mut decoder = ResponseDecoder::new();
+/// while let Some(response) = decoder.decode(&mut buf).unwrap() {
+///     println!("{:?}", response);
+/// }
+/// assert!(buf.is_empty());
+/// # }
+/// ```
+pub struct ResponseDecoder {
+    header: Option<Header>,
+    // Body bytes of the rejected response, which are not dropped yet
+    skip: usize,
+}
+
+impl ResponseDecoder {
+    pub fn new() -> ResponseDecoder {
+        ResponseDecoder {
+            header: None,
+            skip: 0,
+        }
+    }
+
+    /// Attempt to decode next response from the `src`.
+    ///
+    /// Available return values:
+    ///
+    ///   * `Ok(None)` - there is not enough data in `src` yet
+    ///   * `Ok(Some(Response))` - response was decoded and removed from the `src`
+    ///   * `Err(..)` - invalid response header was received
+    ///
+    /// If header is rejected because of the invalid lengths or data type,
+    /// the whole response packet is dropped, so the next `decode` call continues
+    /// with the following response.
+    ///
+    /// `Error::UnknownMagic` and `Error::UnexpectedMagic` mean that the stream
+    /// is out of sync and packet boundaries can not be trusted anymore: `src` is left untouched,
+    /// the same error is returned by all the following calls and connection should be closed.
+    pub fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Response>, Error> {
+        if self.skip > 0 {
+            let length = cmp::min(self.skip, src.len());
+            src.advance(length);
+            self.skip -= length;
+            if self.skip > 0 {
+                return Ok(None);
+            }
+        }
+
+        let header = match self.header.take() {
+            Some(header) => header,
+            None => {
+                if src.len() < HEADER_SIZE {
+                    return Ok(None);
+                }
+                let header = match Header::read(&src[..HEADER_SIZE]) {
+                    Ok(header) => header,
+                    Err(e @ Error::UnknownMagic(..)) | Err(e @ Error::UnexpectedMagic(..)) => return Err(e),
+                    Err(e) => {
+                        // Packet length is still known, so only this response is dropped
+                        self.skip = NetworkEndian::read_u32(&src[8..12]) as usize;
+                        src.advance(HEADER_SIZE);
+                        return Err(e);
+                    },
+                };
+                src.advance(HEADER_SIZE);
+                header
+            },
+        };
+
+        let body_length = header.body_length as usize;
+        if src.len() < body_length {
+            self.header = Some(header);
+            return Ok(None);
+        }
+
+        let body = src.split_to(body_length).freeze();
+
+        Ok(Some(Response::from_parts(header, body)))
+    }
+
+    /// Check if decoder is in the middle of the response.
+    pub fn is_pending(&self) -> bool {
+        self.header.is_some() || self.skip > 0
+    }
+}
+
+impl Default for ResponseDecoder {
+    fn default() -> Self {
+        ResponseDecoder::new()
+    }
+}
+
+impl fmt::Debug for ResponseDecoder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ResponseDecoder")
+            .field("header", &self.header)
+            .field("skip", &self.skip)
+            .finish()
+    }
+}
@@ -0,0 +1,90 @@
+use std::io;
+
+use enum_primitive::FromPrimitive;
+use byteorder::{NetworkEndian, ReadBytesExt};
+
+use super::{Magic, Command, DataType, Status, Error};
+use ::framing::HEADER_SIZE;
+
+/// Parsed fixed-size part of the response packet.
+#[derive(Debug)]
+pub struct Header {
+    pub magic: Magic,
+    pub opcode: Command,
+    pub key_length: u16,
+    pub extras_length: u8,
+    pub data_type: DataType,
+    pub status: Status,
+    pub body_length: u32,
+    pub opaque: u32,
+    pub cas: u64,
+}
+
+impl Header {
+    /// Parse header from the first `HEADER_SIZE` bytes of `src`.
+    ///
+    /// Caller should ensure that `src` is long enough.
+    pub fn read(src: &[u8]) -> Result<Header, Error> {
+        let mut cursor = io::Cursor::new(src);
+
+        let magic = cursor.read_u8()?;
+        let magic = Magic::from_u8(magic).ok_or_else(|| Error::UnknownMagic(magic))?;
+        if magic != Magic::Response {
+            return Err(Error::UnexpectedMagic(magic));
+        }
+        let opcode = cursor.read_u8()?;
+        let key_length = cursor.read_u16::<NetworkEndian>()?;
+        let extras_length = cursor.read_u8()?;
+        let data_type = cursor.read_u8()?;
+        let status = cursor.read_u16::<NetworkEndian>()?;
+
+        let header = Header {
+            magic: magic,
+            opcode: Command::from(opcode),
+            key_length: key_length,
+            extras_length: extras_length,
+            data_type: DataType::from_u8(data_type).ok_or_else(|| Error::UnknownDataType(data_type))?,
+            status: Status::from(status),
+            body_length: cursor.read_u32::<NetworkEndian>()?,
+            opaque: cursor.read_u32::<NetworkEndian>()?,
+            cas: cursor.read_u64::<NetworkEndian>()?,
+        };
+
+        if header.extras_length as usize + header.key_length as usize > header.body_length as usize {
+            return Err(Error::InvalidBodyLength(header.body_length as usize));
+        }
+
+        if header.status == Status::Ok {
+            header.validate()?;
+        }
+
+        Ok(header)
+    }
+
+    /// Check successful response parts against the command requirements.
+    fn validate(&self) -> Result<(), Error> {
+        match self.opcode.response_extras_len() {
+            Some(expected) if expected != self.extras_length as usize => {
+                return Err(Error::InvalidLength {
+                    expected: expected,
+                    actual: self.extras_length as usize,
+                });
+            },
+            _ => {},
+        }
+
+        let value_length = self.body_length as usize - self.extras_length as usize - self.key_length as usize;
+        match self.opcode.response_value_len() {
+            Some(expected) if expected != value_length => Err(Error::InvalidLength {
+                expected: expected,
+                actual: value_length,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Full packet length, including header itself.
+    pub fn packet_length(&self) -> usize {
+        HEADER_SIZE + self.body_length as usize
+    }
+}
@@ -0,0 +1,547 @@
+use std::io;
+use std::fmt;
+
+use bytes::{Bytes, BytesMut, BufMut};
+use byteorder::{ByteOrder, NetworkEndian};
+
+use enum_primitive::FromPrimitive;
+
+use super::{Magic, Command, DataType, VBucketState, Error};
+use super::extras::{self, Extras};
+use super::framing::{self, HEADER_SIZE};
+// Header parsing is shared with the async framing
+pub use self::header::Header;
+pub use self::builder::ResponseBuilder;
+pub use self::decoder::ResponseDecoder;
+
+mod header;
+mod builder;
+mod decoder;
+
+/// Response status variants
+///
+/// Unknown statuses are preserved as `Status::Unknown`,
+/// so packets of the newer protocol versions can still be decoded and forwarded.
+#[derive(Debug, PartialEq, Copy, Clone)]
+#[non_exhaustive]
+pub enum Status {
+    Ok,
+    KeyNotFound,
+    KeyExists,
+    ValueTooLarge,
+    InvalidArguments,
+    ItemNotStored,
+    IncrDecrOnNonNumericValue,
+    VBucketBelongsToAnotherServer,
+    /// `0x0020`, as in the memcached `protocol_binary.h`.
+    AuthenticationError,
+    /// `0x0021`, as in the memcached `protocol_binary.h`.
+    AuthenticationContinue,
+    UnknownCommand,
+    OutOfMemory,
+    NotSupported,
+    InternalError,
+    Busy,
+    TemporaryFailure,
+    /// Status, which is not known to this crate.
+    Unknown(u16),
+}
+
+/// Conversion from the raw status, unknown values are kept as `Status::Unknown`.
+///
+/// # Examples
+///
+/// ```rust
+/// use memcache_proto::Status;
+///
+/// assert_eq!(Status::from(0x0001), Status::KeyNotFound);
+/// assert_eq!(Status::from(0x0020), Status::AuthenticationError);
+/// // Not assigned in memcached, ex. `Locked` of the other servers
+/// assert_eq!(Status::from(0x0009), Status::Unknown(0x0009));
+/// ```
+impl From<u16> for Status {
+    fn from(status: u16) -> Status {
+        match status {
+            0x0000 => Status::Ok,
+            0x0001 => Status::KeyNotFound,
+            0x0002 => Status::KeyExists,
+            0x0003 => Status::ValueTooLarge,
+            0x0004 => Status::InvalidArguments,
+            0x0005 => Status::ItemNotStored,
+            0x0006 => Status::IncrDecrOnNonNumericValue,
+            0x0007 => Status::VBucketBelongsToAnotherServer,
+            0x0020 => Status::AuthenticationError,
+            0x0021 => Status::AuthenticationContinue,
+            0x0081 => Status::UnknownCommand,
+            0x0082 => Status::OutOfMemory,
+            0x0083 => Status::NotSupported,
+            0x0084 => Status::InternalError,
+            0x0085 => Status::Busy,
+            0x0086 => Status::TemporaryFailure,
+            status => Status::Unknown(status),
+        }
+    }
+}
+
+impl From<Status> for u16 {
+    fn from(status: Status) -> u16 {
+        match status {
+            Status::Ok => 0x0000,
+            Status::KeyNotFound => 0x0001,
+            Status::KeyExists => 0x0002,
+            Status::ValueTooLarge => 0x0003,
+            Status::InvalidArguments => 0x0004,
+            Status::ItemNotStored => 0x0005,
+            Status::IncrDecrOnNonNumericValue => 0x0006,
+            Status::VBucketBelongsToAnotherServer => 0x0007,
+            Status::AuthenticationError => 0x0020,
+            Status::AuthenticationContinue => 0x0021,
+            Status::UnknownCommand => 0x0081,
+            Status::OutOfMemory => 0x0082,
+            Status::NotSupported => 0x0083,
+            Status::InternalError => 0x0084,
+            Status::Busy => 0x0085,
+            Status::TemporaryFailure => 0x0086,
+            Status::Unknown(status) => status,
+        }
+    }
+}
+
+impl Status {
+    pub fn is_ok(&self) -> bool {
+        *self == Status::Ok
+    }
+
+    /// Convert status into a `Result`, non-`Ok` statuses are becoming
+    /// an [Error::Status](enum.Error.html#variant.Status).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use memcache_proto::Status;
+    ///
+    /// assert!(Status::Ok.into_result().is_ok());
+    /// assert!(Status::KeyNotFound.into_result().is_err());
+    /// ```
+    pub fn into_result(self) -> Result<(), Error> {
+        match self {
+            Status::Ok => Ok(()),
+            status => Err(Error::from(status)),
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match *self {
+            Status::Ok => "no error",
+            Status::KeyNotFound => "key not found",
+            Status::KeyExists => "key exists",
+            Status::ValueTooLarge => "value too large",
+            Status::InvalidArguments => "invalid arguments",
+            Status::ItemNotStored => "item not stored",
+            Status::IncrDecrOnNonNumericValue => "incr/decr on non-numeric value",
+            Status::VBucketBelongsToAnotherServer => "the vbucket belongs to another server",
+            Status::AuthenticationError => "authentication error",
+            Status::AuthenticationContinue => "authentication continue",
+            Status::UnknownCommand => "unknown command",
+            Status::OutOfMemory => "out of memory",
+            Status::NotSupported => "not supported",
+            Status::InternalError => "internal error",
+            Status::Busy => "busy",
+            Status::TemporaryFailure => "temporary failure",
+            Status::Unknown(status) => return write!(f, "unknown status 0x{:04x}", status),
+        };
+
+        f.write_str(message)
+    }
+}
+
+/// Parsed `memcached` response.
+pub struct Response {
+    magic: Magic,
+    opcode: Command,
+    key_length: u16,
+    extras_length: u8,
+    data_type: DataType,
+    status: Status,
+    body_length: u32,
+    opaque: u32,
+    cas: u64,
+    body: Bytes,
+}
+
+impl Response {
+
+    /// Create new [ResponseBuilder](struct.ResponseBuilder.html)
+    pub fn build(command: Command) -> ResponseBuilder {
+        ResponseBuilder::new(command)
+    }
+
+    pub fn magic(&self) -> &Magic {
+        &self.magic
+    }
+
+    pub fn command(&self) -> &Command {
+        &self.opcode
+    }
+
+    pub fn status(&self) -> &Status {
+        &self.status
+    }
+
+    pub fn data_type(&self) -> &DataType {
+        &self.data_type
+    }
+
+    pub fn opaque(&self) -> &u32 {
+        &self.opaque
+    }
+
+    pub fn cas(&self) -> &u64 {
+        &self.cas
+    }
+
+    pub fn extras(&self) -> Option<&[u8]> {
+        if self.extras_length > 0 {
+            let end = self.extras_length as usize;
+            return Some(&self.body[..end]);
+        }
+        None
+    }
+
+    pub fn key(&self) -> Option<&[u8]> {
+        if self.key_length > 0 {
+            let start = self.extras_length as usize;
+            let end = start + self.key_length as usize;
+
+            return Some(&self.body[start..end]);
+        }
+
+        None
+    }
+
+    pub fn value(&self) -> Option<&[u8]> {
+        let start: usize = self.extras_length as usize + self.key_length as usize;
+        if self.body_length as usize > start {
+            return Some(&self.body[start..]);
+        }
+
+        None
+    }
+
+    /// Decode extras into the `E` container.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use memcache_proto::{Response, Command, extras};
+    ///
+    /// let response = Response::build(Command::Get)
+    ///     .extras(Some(extras::Get::new(0xdeadbeef))).unwrap()
+    ///     .value(Some("World"))
+    ///     .finish()
+    ///     .unwrap();
+    /// let extras = response.extras_as::<extras::Get>().unwrap();
+    /// assert_eq!(extras.flags(), 0xdeadbeef);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if `E` is not used in responses for this command
+    /// or if extras length does not match.
+    pub fn extras_as<E: Extras>(&self) -> Result<E, Error> {
+        if !E::response_commands().contains(&self.opcode) {
+            return Err(Error::InvalidExtras(self.opcode));
+        }
+
+        E::read(&mut io::Cursor::new(self.extras().unwrap_or(&[])))
+    }
+
+    /// Item flags from the `Get`-family responses.
+    pub fn flags(&self) -> Result<u32, Error> {
+        self.extras_as::<extras::Get>().map(|extras| extras.flags())
+    }
+
+    /// Counter value from the `Increment`/`Decrement` responses.
+    pub fn counter(&self) -> Result<u64, Error> {
+        match self.opcode {
+            Command::Increment | Command::IncrementQ | Command::Decrement | Command::DecrementQ => {},
+            command => return Err(Error::UnexpectedCommand(command)),
+        }
+
+        match self.value() {
+            Some(value) if value.len() == 8 => Ok(NetworkEndian::read_u64(value)),
+            value => Err(Error::InvalidLength {
+                expected: 8,
+                actual: value.map_or(0, |value| value.len()),
+            }),
+        }
+    }
+
+    /// vBucket state from the `GetVBucket` responses.
+    pub fn vbucket_state(&self) -> Result<VBucketState, Error> {
+        if self.opcode != Command::GetVBucket {
+            return Err(Error::UnexpectedCommand(self.opcode));
+        }
+
+        match self.value() {
+            Some(value) if value.len() == 4 => {
+                let state = NetworkEndian::read_u32(value);
+                VBucketState::from_u32(state).ok_or_else(|| Error::UnknownVBucketState(state))
+            },
+            value => Err(Error::InvalidLength {
+                expected: 4,
+                actual: value.map_or(0, |value| value.len()),
+            }),
+        }
+    }
+
+    /// Same as [Response::extras](#method.extras), but returns a reference-counted
+    /// slice of the response body without copying it.
+    pub fn extras_bytes(&self) -> Option<Bytes> {
+        if self.extras_length > 0 {
+            return Some(self.body.slice(0, self.extras_length as usize));
+        }
+
+        None
+    }
+
+    /// Same as [Response::key](#method.key), but returns a reference-counted
+    /// slice of the response body without copying it.
+    pub fn key_bytes(&self) -> Option<Bytes> {
+        if self.key_length > 0 {
+            let start = self.extras_length as usize;
+            let end = start + self.key_length as usize;
+
+            return Some(self.body.slice(start, end));
+        }
+
+        None
+    }
+
+    /// Same as [Response::value](#method.value), but returns a reference-counted
+    /// slice of the response body without copying it.
+    pub fn value_bytes(&self) -> Option<Bytes> {
+        let start: usize = self.extras_length as usize + self.key_length as usize;
+        if self.body_length as usize > start {
+            return Some(self.body.slice_from(start));
+        }
+
+        None
+    }
+
+    /// Attempt to read a response from the `raw`.
+    ///
+    /// Available return values:
+    ///
+    ///   * `Ok(None)` - there is not enough data to read full response
+    ///   * `Ok(Some(Response))` - read was successful
+    ///   * `Err(..)` - invalid response was read
+    ///
+    /// It might be required to remove read bytes from the `raw`
+    /// based on the implementation specific.
+    /// `Response::len()` method should help in that,
+    /// or [ResponseDecoder](struct.ResponseDecoder.html) can be used instead.
+    pub fn read(src: &[u8]) -> Result<Option<Response>, Error> {
+        if src.len() < HEADER_SIZE {
+            return Ok(None);
+        }
+
+        let header = Header::read(&src[..HEADER_SIZE])?;
+        let packet_length = header.packet_length();
+        if src.len() < packet_length {  // Body is not received yet
+            return Ok(None);
+        }
+
+        let body = Bytes::from(&src[HEADER_SIZE..packet_length]);
+
+        Ok(Some(Response::from_parts(header, body)))
+    }
+
+    /// Attempt to read a response from the `src` without copying its body.
+    ///
+    /// Return values are the same as for the [Response::read](#method.read),
+    /// but the whole packet is split off the `src` if read was successful
+    /// and response body is referencing the same memory region.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate bytes;
+    /// extern crate memcache_proto;
+    ///
+    /// use bytes::BytesMut;
+    /// use memcache_proto::{Response, Command};
+    ///
+    /// # fn main() {
+    /// let mut raw: Vec<u8> = vec![];
+    /// Response::build(Command::Version).value(Some("1.5.0")).finish().unwrap().write(&mut raw).unwrap();
+    ///
+    /// let mut buf = BytesMut::from(raw);
+    /// let response = Response::read_bytes(&mut buf).unwrap().unwrap();
+    /// assert_eq!(response.value_bytes().unwrap(), "1.5.0");
+    /// assert!(buf.is_empty());
+    /// # }
+    /// ```
+    pub fn read_bytes(src: &mut BytesMut) -> Result<Option<Response>, Error> {
+        if src.len() < HEADER_SIZE {
+            return Ok(None);
+        }
+
+        let header = Header::read(&src[..HEADER_SIZE])?;
+        if src.len() < header.packet_length() {  // Body is not received yet
+            return Ok(None);
+        }
+
+        let body = src.split_to(header.packet_length()).freeze().slice_from(HEADER_SIZE);
+
+        Ok(Some(Response::from_parts(header, body)))
+    }
+
+    /// Write serialized response as a bytes into `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use memcache_proto::{Response, Command, Status};
+    ///
+    /// let response = Response::build(Command::Get)
+    ///     .status(Status::KeyNotFound)
+    ///     .value(Some("Not found"))
+    ///     .finish()
+    ///     .unwrap();
+    /// let mut buf: Vec<u8> = Vec::with_capacity(response.len());
+    /// response.write(&mut buf).expect("buf filled with a bytes");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an [Error::NotEnoughCapacity](enum.Error.html#variant.NotEnoughCapacity)
+    /// if `out` is not able to fit the whole packet, nothing is written in that case.
+    pub fn write<T: BufMut>(&self, out: &mut T) -> Result<(), Error> {
+        if out.remaining_mut() < self.len() {
+            return Err(Error::NotEnoughCapacity {
+                required: self.len(),
+                remaining: out.remaining_mut(),
+            });
+        }
+
+        out.put_u8(self.magic as u8);
+        out.put_u8(self.opcode.into());
+        out.put_u16::<NetworkEndian>(self.key_length);
+        out.put_u8(self.extras_length);
+        out.put_u8(self.data_type as u8);
+        out.put_u16::<NetworkEndian>(self.status.into());
+        out.put_u32::<NetworkEndian>(self.body_length);
+        out.put_u32::<NetworkEndian>(self.opaque);
+        out.put_u64::<NetworkEndian>(self.cas);
+        out.put_slice(&self.body);
+
+        Ok(())
+    }
+
+    /// Write serialized response into the blocking `std::io` writer.
+    ///
+    /// Response is serialized into an intermediate buffer first,
+    /// so `out` receives the whole packet with a single `write_all` call.
+    pub fn write_to<W: io::Write>(&self, out: &mut W) -> Result<(), Error> {
+        let mut buf = Vec::with_capacity(self.len());
+        self.write(&mut buf)?;
+        out.write_all(&buf)?;
+        Ok(())
+    }
+
+    /// Read a single response from the blocking `std::io` reader.
+    ///
+    /// Reads the 24-byte header first, validates it and then reads exactly
+    /// the body length bytes, nothing past the packet is consumed from `src`.
+    ///
+    /// `Ok(None)` is returned if `src` is at EOF before the first header byte,
+    /// ex. server closed connection between the responses.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::net::TcpStream;
+    /// use memcache_proto::{Request, Response};
+    ///
+    /// # fn run() -> Result<(), memcache_proto::Error> {
+    /// let mut stream = TcpStream::connect("127.0.0.1:11211")?;
+    /// Request::version().write_to(&mut stream)?;
+    ///
+    /// if let Some(response) = Response::read_from(&mut stream)? {
+    ///     println!("{:?}", response.value());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # fn main() { run().unwrap(); }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails with an `io::ErrorKind::UnexpectedEof` I/O error if `src` ends in the middle
+    /// of a packet, otherwise errors are the same as for [Response::read](#method.read).
+    pub fn read_from<R: io::Read>(src: &mut R) -> Result<Option<Response>, Error> {
+        let mut header = [0; HEADER_SIZE];
+        if !framing::read_header(src, &mut header)? {
+            return Ok(None);
+        }
+
+        let header = Header::read(&header)?;
+        let body = framing::read_body(src, HEADER_SIZE, header.body_length as usize)?;
+
+        Ok(Some(Response::from_parts(header, Bytes::from(body))))
+    }
+
+    pub fn len(&self) -> usize {
+        HEADER_SIZE + self.body_length as usize
+    }
+
+    fn from_parts(header: Header, body: Bytes) -> Response {
+        Response {
+            magic: header.magic,
+            opcode: header.opcode,
+            key_length: header.key_length,
+            extras_length: header.extras_length,
+            data_type: header.data_type,
+            status: header.status,
+            body_length: header.body_length,
+            opaque: header.opaque,
+            cas: header.cas,
+            body: body,
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.status.is_ok()
+    }
+
+    pub fn is_err(&self) -> bool {
+        !self.is_ok()
+    }
+}
+
+impl fmt::Debug for Response {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut resp = f.debug_struct("Response");
+
+        resp
+            .field("command", &self.opcode)
+            .field("status", &self.status);
+
+        if let Some(key) = self.key() {
+            resp.field("key", &key);
+        }
+
+        if let Some(value) = self.value() {
+            resp.field("value", &value);
+        }
+
+        if let Some(extras) = self.extras() {
+            resp.field("extras", &extras);
+        }
+
+        resp.finish()
+    }
+}
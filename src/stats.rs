@@ -0,0 +1,231 @@
+use std::fmt;
+use std::collections::HashMap;
+use std::collections::hash_map::Iter;
+
+use super::{Command, Response, Status, Error};
+
+/// Statistics groups, which can be requested with
+/// [Request::stat](struct.Request.html#method.stat).
+///
+/// # Examples
+///
+/// ```rust
+/// use memcache_proto::{Request, StatGroup};
+///
+/// let request = Request::stat(Some(StatGroup::Items));
+/// assert_eq!(request.key(), Some(&b"items"[..]));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StatGroup {
+    Settings,
+    Items,
+    Slabs,
+    Conns,
+    DetailDump,
+}
+
+impl StatGroup {
+
+    /// Group name, as it is sent in the request key.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            StatGroup::Settings => "settings",
+            StatGroup::Items => "items",
+            StatGroup::Slabs => "slabs",
+            StatGroup::Conns => "conns",
+            StatGroup::DetailDump => "detail dump",
+        }
+    }
+}
+
+impl AsRef<[u8]> for StatGroup {
+    fn as_ref(&self) -> &[u8] {
+        self.as_str().as_bytes()
+    }
+}
+
+impl fmt::Display for StatGroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Statistics received from the server.
+///
+/// Raw values are available with [Stats::get](#method.get),
+/// well-known numeric statistics have typed getters.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Stats(HashMap<String, String>);
+
+impl Stats {
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(|value| value.as_str())
+    }
+
+    /// Numeric statistic value, `None` is returned if it is missing or is not a number.
+    pub fn get_u64(&self, name: &str) -> Option<u64> {
+        self.get(name).and_then(|value| value.parse().ok())
+    }
+
+    /// Current number of items stored.
+    pub fn curr_items(&self) -> Option<u64> {
+        self.get_u64("curr_items")
+    }
+
+    /// Total number of items stored since the server start.
+    pub fn total_items(&self) -> Option<u64> {
+        self.get_u64("total_items")
+    }
+
+    /// Number of keys that have been requested and found present.
+    pub fn get_hits(&self) -> Option<u64> {
+        self.get_u64("get_hits")
+    }
+
+    /// Number of items that have been requested and not found.
+    pub fn get_misses(&self) -> Option<u64> {
+        self.get_u64("get_misses")
+    }
+
+    /// Number of valid items removed from cache to free memory for new items.
+    pub fn evictions(&self) -> Option<u64> {
+        self.get_u64("evictions")
+    }
+
+    /// Current number of bytes used to store items.
+    pub fn bytes(&self) -> Option<u64> {
+        self.get_u64("bytes")
+    }
+
+    /// Number of open connections.
+    pub fn curr_connections(&self) -> Option<u64> {
+        self.get_u64("curr_connections")
+    }
+
+    /// Number of seconds since the server start.
+    pub fn uptime(&self) -> Option<u64> {
+        self.get_u64("uptime")
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> Iter<'_, String, String> {
+        self.0.iter()
+    }
+
+    pub fn into_inner(self) -> HashMap<String, String> {
+        self.0
+    }
+}
+
+impl From<Stats> for HashMap<String, String> {
+    fn from(stats: Stats) -> Self {
+        stats.0
+    }
+}
+
+/// Collector for the `Stat` responses.
+///
+/// Server responds to the `Stat` request with a separate response for each statistic,
+/// stream is terminated by a response without a key.
+///
+/// Keys and values are expected to be ASCII strings,
+/// invalid UTF-8 sequences are replaced with `U+FFFD`.
+///
+/// # Examples
+///
+/// ```rust
+/// use memcache_proto::{Command, Response, StatsCollector};
+///
+/// let mut collector = StatsCollector::new();
+///
+/// let stat = Response::build(Command::Stat).key(Some("curr_items")).value(Some("42")).finish().unwrap();
+/// assert!(!collector.push(stat).unwrap());
+///
+/// let end = Response::build(Command::Stat).finish().unwrap();
+/// assert!(collector.push(end).unwrap());
+///
+/// assert_eq!(collector.stats().curr_items(), Some(42));
+/// ```
+pub struct StatsCollector {
+    stats: Stats,
+    done: bool,
+}
+
+impl StatsCollector {
+
+    pub fn new() -> StatsCollector {
+        StatsCollector {
+            stats: Stats::default(),
+            done: false,
+        }
+    }
+
+    /// Add next response of the stream.
+    ///
+    /// Returns `true` when the stream is finished and no more responses are expected.
+    ///
+    /// # Errors
+    ///
+    /// Fails if response is not a `Stat` one or if server responded with an error status.
+    ///
+    /// Responses received after the terminating one are rejected with `Error::UnexpectedCommand`,
+    /// so stats of the next request are never merged into this one.
+    pub fn push(&mut self, response: Response) -> Result<bool, Error> {
+        if self.done || *response.command() != Command::Stat {
+            return Err(Error::UnexpectedCommand(*response.command()));
+        }
+
+        match *response.status() {
+            Status::Ok => {},
+            status => return Err(Error::Status(status)),
+        }
+
+        let key = match response.key() {
+            Some(key) => String::from_utf8_lossy(key).into_owned(),
+            None => {
+                self.done = true;
+                return Ok(true);
+            },
+        };
+        let value = String::from_utf8_lossy(response.value().unwrap_or(&[])).into_owned();
+        self.stats.0.insert(key, value);
+
+        Ok(false)
+    }
+
+    /// Check if stream terminating response was received.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    pub fn into_stats(self) -> Stats {
+        self.stats
+    }
+}
+
+impl Default for StatsCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for StatsCollector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StatsCollector")
+            .field("stats", &self.stats)
+            .field("done", &self.done)
+            .finish()
+    }
+}
@@ -0,0 +1,239 @@
+use std::fmt;
+
+use byteorder::{ByteOrder, NetworkEndian};
+use enum_primitive::FromPrimitive;
+
+use super::{Command, Request, VBucketState, Error};
+use super::extras::Expiry;
+
+const EXTRAS_LENGTH: usize = 8;
+const MUTATION_EXTRAS_LENGTH: usize = 16;
+
+/// Flags of the TAP messages.
+///
+/// Reference: https://github.com/memcached/memcached/wiki/BinaryProtocolRevamped#tap-protocol
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct TapMessageFlags(u16);
+
+impl TapMessageFlags {
+    /// Server expects the client to acknowledge this message.
+    pub const ACK: TapMessageFlags = TapMessageFlags(0x01);
+    /// Message is sent without the item value.
+    pub const NO_VALUE: TapMessageFlags = TapMessageFlags(0x02);
+    /// Engine-private section is encoded in the network byte order.
+    pub const NETWORK_BYTE_ORDER: TapMessageFlags = TapMessageFlags(0x04);
+
+    /// Create flags from the raw protocol value, unknown bits are preserved.
+    pub fn from_bits(bits: u16) -> TapMessageFlags {
+        TapMessageFlags(bits)
+    }
+
+    pub fn bits(&self) -> u16 {
+        self.0
+    }
+
+    pub fn contains(&self, other: TapMessageFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+/// Decoded TAP message.
+///
+/// TAP messages are sent by the server as request packets,
+/// with a TAP-specific extras section and an engine-private section,
+/// which is placed between extras and key.
+///
+/// Following commands are supported: `TapMutation`, `TapDelete`, `TapFlush`,
+/// `TapOpaque`, `TapVBucketSet`, `TapCheckpointStart` and `TapCheckpointEnd`.
+///
+/// # Examples
+///
+/// ```rust
+/// use memcache_proto::{Command, Request, TapMessage};
+///
+/// let raw: Vec<u8> = vec![
+///     0x80, 0x42, 0x00, 0x05,
+///     0x08, 0x00, 0x00, 0x07,
+///     0x00, 0x00, 0x00, 0x0f,
+///     0x00, 0x00, 0x00, 0x00,
+///     0x00, 0x00, 0x00, 0x00,
+///     0x00, 0x00, 0x00, 0x00,
+///     // engine-private length, flags, ttl and reserved bytes
+///     0x00, 0x02, 0x00, 0x01,
+///     0xff, 0x00, 0x00, 0x00,
+///     // engine-private section and key
+///     0xbe, 0xef, 0x48, 0x65,
+///     0x6c, 0x6c, 0x6f,
+/// ];
+///
+/// let request = Request::read(&raw).unwrap().unwrap();
+/// let message = TapMessage::from_request(&request).unwrap();
+///
+/// assert_eq!(message.command(), Command::TapDelete);
+/// assert_eq!(message.vbucket_id(), 7);
+/// assert_eq!(message.engine_private(), &[0xbe, 0xef]);
+/// assert_eq!(message.key(), Some(&b"Hello"[..]));
+/// ```
+pub struct TapMessage {
+    command: Command,
+    vbucket_id: u16,
+    opaque: u32,
+    cas: u64,
+    engine_private: Vec<u8>,
+    flags: TapMessageFlags,
+    ttl: u8,
+    item_flags: Option<u32>,
+    expiration: Option<Expiry>,
+    key: Option<Vec<u8>>,
+    value: Option<Vec<u8>>,
+}
+
+impl TapMessage {
+
+    /// Decode TAP message from the request packet.
+    ///
+    /// # Errors
+    ///
+    /// Fails if request command is not a TAP message one,
+    /// if extras section is too short or if engine-private section
+    /// does not fit into the packet body.
+    pub fn from_request(request: &Request) -> Result<TapMessage, Error> {
+        let command = *request.command();
+        let extras_length = match command {
+            Command::TapMutation => MUTATION_EXTRAS_LENGTH,
+            Command::TapDelete | Command::TapFlush | Command::TapOpaque | Command::TapVBucketSet |
+            Command::TapCheckpointStart | Command::TapCheckpointEnd => EXTRAS_LENGTH,
+            command => return Err(Error::UnexpectedCommand(command)),
+        };
+
+        let extras = request.extras().unwrap_or(&[]);
+        if extras.len() < extras_length {
+            return Err(Error::InvalidLength {
+                expected: extras_length,
+                actual: extras.len(),
+            });
+        }
+
+        let (item_flags, expiration) = if command == Command::TapMutation {
+            (
+                Some(NetworkEndian::read_u32(&extras[8..12])),
+                Some(Expiry::from_seconds(NetworkEndian::read_u32(&extras[12..16]))),
+            )
+        } else {
+            (None, None)
+        };
+
+        // Request decoding is not aware of the engine-private section,
+        // so it is split from the key and value parts here.
+        let key_length = request.key().map_or(0, |key| key.len());
+        let mut payload = Vec::with_capacity(key_length + request.value().map_or(0, |value| value.len()));
+        payload.extend_from_slice(request.key().unwrap_or(&[]));
+        payload.extend_from_slice(request.value().unwrap_or(&[]));
+
+        let engine_length = NetworkEndian::read_u16(&extras[0..2]) as usize;
+        if engine_length + key_length > payload.len() {
+            return Err(Error::InvalidBodyLength(extras.len() + payload.len()));
+        }
+
+        let value = payload.split_off(engine_length + key_length);
+        let key = payload.split_off(engine_length);
+
+        Ok(TapMessage {
+            command: command,
+            vbucket_id: *request.vbucket_id(),
+            opaque: *request.opaque(),
+            cas: *request.cas(),
+            engine_private: payload,
+            flags: TapMessageFlags::from_bits(NetworkEndian::read_u16(&extras[2..4])),
+            ttl: extras[4],
+            item_flags: item_flags,
+            expiration: expiration,
+            key: if key.is_empty() { None } else { Some(key) },
+            value: if value.is_empty() { None } else { Some(value) },
+        })
+    }
+
+    pub fn command(&self) -> Command {
+        self.command
+    }
+
+    pub fn vbucket_id(&self) -> u16 {
+        self.vbucket_id
+    }
+
+    pub fn opaque(&self) -> u32 {
+        self.opaque
+    }
+
+    pub fn cas(&self) -> u64 {
+        self.cas
+    }
+
+    /// Engine-specific data, empty if server did not send any.
+    pub fn engine_private(&self) -> &[u8] {
+        &self.engine_private
+    }
+
+    pub fn flags(&self) -> TapMessageFlags {
+        self.flags
+    }
+
+    /// Number of hops this message is allowed to pass.
+    pub fn ttl(&self) -> u8 {
+        self.ttl
+    }
+
+    /// Item flags, available for `TapMutation` messages only.
+    pub fn item_flags(&self) -> Option<u32> {
+        self.item_flags
+    }
+
+    /// Item expiration, available for `TapMutation` messages only.
+    pub fn expiration(&self) -> Option<Expiry> {
+        self.expiration
+    }
+
+    pub fn key(&self) -> Option<&[u8]> {
+        self.key.as_ref().map(|key| key.as_slice())
+    }
+
+    pub fn value(&self) -> Option<&[u8]> {
+        self.value.as_ref().map(|value| value.as_slice())
+    }
+
+    /// New vBucket state from the `TapVBucketSet` messages.
+    pub fn vbucket_state(&self) -> Result<VBucketState, Error> {
+        if self.command != Command::TapVBucketSet {
+            return Err(Error::UnexpectedCommand(self.command));
+        }
+
+        match self.value() {
+            Some(value) if value.len() == 4 => {
+                let state = NetworkEndian::read_u32(value);
+                VBucketState::from_u32(state).ok_or_else(|| Error::UnknownVBucketState(state))
+            },
+            value => Err(Error::InvalidLength {
+                expected: 4,
+                actual: value.map_or(0, |value| value.len()),
+            }),
+        }
+    }
+}
+
+impl fmt::Debug for TapMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TapMessage")
+            .field("command", &self.command)
+            .field("vbucket_id", &self.vbucket_id)
+            .field("opaque", &self.opaque)
+            .field("cas", &self.cas)
+            .field("engine_private", &self.engine_private())
+            .field("flags", &self.flags)
+            .field("ttl", &self.ttl)
+            .field("item_flags", &self.item_flags)
+            .field("expiration", &self.expiration)
+            .field("key", &self.key())
+            .field("value", &self.value())
+            .finish()
+    }
+}
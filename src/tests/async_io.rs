@@ -0,0 +1,183 @@
+use std::io;
+use std::pin::Pin;
+use std::future::Future;
+use std::task::{Context, Poll, Waker, RawWaker, RawWakerVTable};
+
+use futures_io::{AsyncRead, AsyncWrite};
+use futures_core::Stream;
+use futures_sink::Sink;
+
+use ::{Command, Request, Response, ClientTransport, Error};
+
+fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(::std::ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+    unsafe { Waker::from_raw(RawWaker::new(::std::ptr::null(), &VTABLE)) }
+}
+
+// Every test I/O object makes progress on each other poll, so busy polling is fine here
+fn poll_until_ready<T, F: FnMut(&mut Context) -> Poll<T>>(mut f: F) -> T {
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(value) = f(&mut cx) {
+            return value;
+        }
+    }
+}
+
+fn block_on<F: Future + Unpin>(mut future: F) -> F::Output {
+    poll_until_ready(|cx| Pin::new(&mut future).poll(cx))
+}
+
+// I/O object, which is not ready on every other call and transfers at most 5 bytes at once
+struct Mock {
+    input: io::Cursor<Vec<u8>>,
+    output: Vec<u8>,
+    ready: bool,
+}
+
+impl Mock {
+    fn new(input: Vec<u8>) -> Mock {
+        Mock {
+            input: io::Cursor::new(input),
+            output: vec![],
+            ready: false,
+        }
+    }
+
+    fn toggle(&mut self) -> bool {
+        self.ready = !self.ready;
+        self.ready
+    }
+}
+
+impl AsyncRead for Mock {
+    fn poll_read(self: Pin<&mut Self>, _: &mut Context, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if !this.toggle() {
+            return Poll::Pending;
+        }
+        let length = buf.len().min(5);
+        Poll::Ready(io::Read::read(&mut this.input, &mut buf[..length]))
+    }
+}
+
+impl AsyncWrite for Mock {
+    fn poll_write(self: Pin<&mut Self>, _: &mut Context, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if !this.toggle() {
+            return Poll::Pending;
+        }
+        let length = buf.len().min(5);
+        this.output.extend_from_slice(&buf[..length]);
+        Poll::Ready(Ok(length))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+fn responses() -> Vec<u8> {
+    let mut raw: Vec<u8> = vec![];
+    Response::build(Command::Version).value(Some("1.5.0")).finish().unwrap().write(&mut raw).unwrap();
+    Response::build(Command::Noop).finish().unwrap().write(&mut raw).unwrap();
+    raw
+}
+
+#[test]
+fn test_write_async() {
+    let mut out = Mock::new(vec![]);
+    block_on(Request::get(b"Hello").write_async(&mut out)).unwrap();
+
+    let mut expected: Vec<u8> = vec![];
+    Request::get(b"Hello").write(&mut expected).unwrap();
+    assert_eq!(out.output, expected);
+}
+
+#[test]
+fn test_write_async_invalid() {
+    let mut out = Mock::new(vec![]);
+    match block_on(Request::new(Command::Get).write_async(&mut out)) {
+        Err(Error::MissingKey(Command::Get)) => {},
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert!(out.output.is_empty());
+}
+
+#[test]
+fn test_read_async() {
+    let mut src = Mock::new(responses());
+
+    let first = block_on(Response::read_async(&mut src)).unwrap().unwrap();
+    let second = block_on(Response::read_async(&mut src)).unwrap().unwrap();
+
+    assert_eq!(first.value(), Some(&b"1.5.0"[..]));
+    assert_eq!(*second.command(), Command::Noop);
+    assert!(block_on(Response::read_async(&mut src)).unwrap().is_none());
+}
+
+#[test]
+fn test_read_async_truncated() {
+    let mut raw = responses();
+    raw.truncate(26);
+    let mut src = Mock::new(raw);
+
+    match block_on(Response::read_async(&mut src)) {
+        Err(Error::Io(ref error)) if error.kind() == io::ErrorKind::UnexpectedEof => {},
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn test_transport() {
+    let mut transport = ClientTransport::new(Mock::new(responses()));
+
+    poll_until_ready(|cx| Pin::new(&mut transport).poll_ready(cx)).unwrap();
+    Pin::new(&mut transport).start_send(Request::version()).unwrap();
+    poll_until_ready(|cx| Pin::new(&mut transport).poll_ready(cx)).unwrap();
+    Pin::new(&mut transport).start_send(Request::noop()).unwrap();
+    assert!(transport.get_ref().output.is_empty());
+    poll_until_ready(|cx| Pin::new(&mut transport).poll_flush(cx)).unwrap();
+    assert_eq!(transport.get_ref().output.len(), 48);
+
+    let first = poll_until_ready(|cx| Pin::new(&mut transport).poll_next(cx)).unwrap().unwrap();
+    let second = poll_until_ready(|cx| Pin::new(&mut transport).poll_next(cx)).unwrap().unwrap();
+    assert_eq!(*first.command(), Command::Version);
+    assert_eq!(*second.command(), Command::Noop);
+    assert!(poll_until_ready(|cx| Pin::new(&mut transport).poll_next(cx)).is_none());
+}
+
+#[test]
+fn test_transport_truncated() {
+    let mut raw = responses();
+    raw.truncate(30);
+    let mut transport = ClientTransport::new(Mock::new(raw));
+
+    assert!(poll_until_ready(|cx| Pin::new(&mut transport).poll_next(cx)).unwrap().is_ok());
+    assert!(poll_until_ready(|cx| Pin::new(&mut transport).poll_next(cx)).unwrap().is_err());
+    assert!(poll_until_ready(|cx| Pin::new(&mut transport).poll_next(cx)).is_none());
+}
+
+#[test]
+fn test_transport_decode_error() {
+    let mut raw = responses();
+    raw[0] = 0x42;
+    let mut transport = ClientTransport::new(Mock::new(raw));
+
+    match poll_until_ready(|cx| Pin::new(&mut transport).poll_next(cx)) {
+        Some(Err(Error::UnknownMagic(0x42))) => {},
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert!(poll_until_ready(|cx| Pin::new(&mut transport).poll_next(cx)).is_none());
+    assert!(poll_until_ready(|cx| Pin::new(&mut transport).poll_next(cx)).is_none());
+}
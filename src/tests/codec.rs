@@ -0,0 +1,108 @@
+use bytes::BytesMut;
+use tokio_codec::{Encoder, Decoder};
+
+use ::{Command, Magic, Request, Response, Error, MemcacheClientCodec, MemcacheServerCodec};
+use extras;
+
+#[test]
+fn test_encode() {
+    let mut codec = MemcacheClientCodec::new();
+    let mut buf = BytesMut::new();
+
+    codec.encode(Request::get(b"Hello"), &mut buf).unwrap();
+    codec.encode(Request::noop(), &mut buf).unwrap();
+
+    let mut expected: Vec<u8> = vec![];
+    Request::get(b"Hello").write(&mut expected).unwrap();
+    Request::noop().write(&mut expected).unwrap();
+    assert_eq!(&buf[..], &expected[..]);
+}
+
+#[test]
+fn test_encode_invalid() {
+    let mut codec = MemcacheClientCodec::new();
+    let mut buf = BytesMut::new();
+
+    assert!(codec.encode(Request::new(Command::Get), &mut buf).is_err());
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn test_decode_partial() {
+    let mut raw: Vec<u8> = vec![];
+    Response::build(Command::Version).value(Some("1.5.0")).finish().unwrap().write(&mut raw).unwrap();
+    Response::build(Command::Noop).finish().unwrap().write(&mut raw).unwrap();
+
+    let mut codec = MemcacheClientCodec::new();
+    let mut buf = BytesMut::new();
+    let mut responses = vec![];
+    for chunk in raw.chunks(7) {
+        buf.extend_from_slice(chunk);
+        while let Some(response) = codec.decode(&mut buf).unwrap() {
+            responses.push(response);
+        }
+    }
+
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0].value(), Some(&b"1.5.0"[..]));
+    assert_eq!(*responses[1].command(), Command::Noop);
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn test_server_round_trip() {
+    let mut raw: Vec<u8> = vec![];
+    Request::set(b"Hello", b"World", extras::Set::default()).unwrap().write(&mut raw).unwrap();
+    Request::get_q(b"Hello").write(&mut raw).unwrap();
+
+    let mut codec = MemcacheServerCodec::new();
+    let mut buf = BytesMut::new();
+    let mut requests = vec![];
+    for chunk in raw.chunks(5) {
+        buf.extend_from_slice(chunk);
+        while let Some(request) = codec.decode(&mut buf).unwrap() {
+            requests.push(request);
+        }
+    }
+
+    assert_eq!(requests.len(), 2);
+    assert_eq!(*requests[0].command(), Command::Set);
+    assert_eq!(requests[0].value(), Some(&b"World"[..]));
+    assert_eq!(*requests[1].command(), Command::GetQ);
+    assert!(buf.is_empty());
+
+    let mut out = BytesMut::new();
+    codec.encode(Response::build(Command::Set).finish().unwrap(), &mut out).unwrap();
+    assert_eq!(out.len(), 24);
+    assert_eq!(out[0], 0x81);
+}
+
+#[test]
+fn test_server_body_limit() {
+    let mut raw: Vec<u8> = vec![];
+    Request::append(b"Hello", [0u8; 64]).write(&mut raw).unwrap();
+    // Only the header is received
+    raw.truncate(24);
+
+    let mut codec = MemcacheServerCodec::with_max_body_length(32);
+    match codec.decode(&mut BytesMut::from(raw)) {
+        Err(Error::BodyLimitExceeded { length: 69, limit: 32 }) => {},
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn test_server_magic() {
+    let mut raw: Vec<u8> = vec![];
+    Response::build(Command::Noop).finish().unwrap().write(&mut raw).unwrap();
+
+    match MemcacheServerCodec::new().decode(&mut BytesMut::from(raw)) {
+        Err(Error::UnexpectedMagic(Magic::Response)) => {},
+        other => panic!("unexpected result {:?}", other),
+    }
+
+    match MemcacheServerCodec::new().decode(&mut BytesMut::from(vec![0x42; 24])) {
+        Err(Error::UnknownMagic(0x42)) => {},
+        other => panic!("unexpected result {:?}", other),
+    }
+}
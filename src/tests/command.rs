@@ -0,0 +1,108 @@
+use ::{Command, Request, RequestRef, Response, Error};
+use extras;
+
+#[test]
+fn test_quiet_pairs() {
+    let pairs = [
+        (Command::Get, Command::GetQ),
+        (Command::GetK, Command::GetKQ),
+        (Command::Set, Command::SetQ),
+        (Command::Delete, Command::DeleteQ),
+        (Command::Increment, Command::IncrementQ),
+        (Command::Flush, Command::FlushQ),
+        (Command::Gat, Command::GatQ),
+        (Command::RDecr, Command::RDecrQ),
+    ];
+
+    for &(loud, quiet) in pairs.iter() {
+        assert!(!loud.is_quiet());
+        assert!(quiet.is_quiet());
+        assert_eq!(loud.quiet(), Some(quiet));
+        assert_eq!(quiet.quiet(), Some(quiet));
+        assert_eq!(quiet.loud(), loud);
+        assert_eq!(loud.loud(), loud);
+    }
+}
+
+#[test]
+fn test_without_quiet_version() {
+    for command in [Command::Noop, Command::Version, Command::Stat, Command::Touch].iter() {
+        assert!(!command.is_quiet());
+        assert_eq!(command.quiet(), None);
+        assert_eq!(command.loud(), *command);
+    }
+}
+
+#[test]
+fn test_request_shape() {
+    assert!(Command::GetKQ.requires_key());
+    assert!(!Command::GetKQ.allows_value());
+    assert_eq!(Command::GetKQ.request_extras_len(), Some(0));
+
+    assert!(Command::Delete.requires_key());
+    assert!(!Command::Delete.allows_value());
+
+    assert!(Command::SetQ.allows_value());
+    assert_eq!(Command::SetQ.request_extras_len(), Some(8));
+    assert_eq!(Command::Decrement.request_extras_len(), Some(20));
+    assert_eq!(Command::Touch.request_extras_len(), Some(4));
+    assert_eq!(Command::Flush.request_extras_len(), None);
+
+    assert!(!Command::Stat.requires_key());
+    assert!(!Command::Noop.requires_key());
+}
+
+#[test]
+fn test_response_shape() {
+    assert_eq!(Command::GetQ.response_extras_len(), Some(4));
+    assert_eq!(Command::GatQ.response_extras_len(), Some(4));
+    assert_eq!(Command::Touch.response_extras_len(), Some(4));
+    assert_eq!(Command::Set.response_extras_len(), Some(0));
+    assert_eq!(Command::IncrementQ.response_extras_len(), Some(0));
+    assert_eq!(Command::IncrementQ.response_value_len(), Some(8));
+    assert_eq!(Command::Get.response_value_len(), None);
+}
+
+#[test]
+fn test_opcode_round_trip() {
+    for opcode in 0..=255_u8 {
+        assert_eq!(u8::from(Command::from(opcode)), opcode);
+    }
+
+    assert_eq!(Command::from(0x47), Command::TapCheckpointEnd);
+    assert_eq!(Command::from(0x48), Command::Unknown(0x48));
+}
+
+#[test]
+fn test_unknown_metadata() {
+    let command = Command::Unknown(0x1f);
+
+    assert!(!command.is_quiet());
+    assert_eq!(command.loud(), command);
+    assert!(!command.requires_key());
+    assert!(command.allows_value());
+    assert_eq!(command.request_extras_len(), None);
+    assert_eq!(command.response_extras_len(), None);
+}
+
+#[test]
+fn test_unknown_assigned_opcode() {
+    let mut request = Request::new(Command::Unknown(0x01));
+    assert_eq!(*request.command(), Command::Set);
+    request.set_extras(Some(extras::Set::default())).unwrap();
+    assert_eq!(*request.into_quiet().command(), Command::SetQ);
+
+    let request = Request::build(Command::Unknown(0x0c)).finish();
+    assert_eq!(*request.command(), Command::GetK);
+
+    match RequestRef::new(Command::Unknown(0x1c)).write(&mut vec![]) {
+        Err(Error::MissingKey(Command::Touch)) => {},
+        other => panic!("Unexpected result: {:?}", other),
+    }
+
+    let response = Response::build(Command::Unknown(0x00))
+        .extras(Some(extras::Get::new(0xdeadbeef))).unwrap()
+        .finish().unwrap();
+    assert_eq!(*response.command(), Command::Get);
+    assert_eq!(response.flags().unwrap(), 0xdeadbeef);
+}
@@ -0,0 +1,112 @@
+use std::io::{self, Read};
+
+use ::{Command, Request, Response, Status, Error, DEFAULT_MAX_BODY_LENGTH};
+
+// Reader, which returns data in small chunks and is interrupted between them
+struct Chunked {
+    data: io::Cursor<Vec<u8>>,
+    interrupted: bool,
+}
+
+impl Read for Chunked {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.interrupted = !self.interrupted;
+        if self.interrupted {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"));
+        }
+
+        let length = buf.len().min(3);
+        self.data.read(&mut buf[..length])
+    }
+}
+
+fn assert_truncated(result: Result<Option<Response>, Error>) {
+    match result {
+        Err(Error::Io(ref error)) if error.kind() == io::ErrorKind::UnexpectedEof => {},
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn test_request_round_trip() {
+    let mut raw: Vec<u8> = vec![];
+    Request::get(b"Hello").write_to(&mut raw).unwrap();
+    Request::append(b"Hello", b"World").write_to(&mut raw).unwrap();
+
+    let mut src = Chunked { data: io::Cursor::new(raw), interrupted: false };
+    let first = Request::read_from(&mut src).unwrap().unwrap();
+    let second = Request::read_from(&mut src).unwrap().unwrap();
+
+    assert_eq!(*first.command(), Command::Get);
+    assert_eq!(*second.command(), Command::Append);
+    assert_eq!(second.value(), Some(&b"World"[..]));
+    assert!(Request::read_from(&mut src).unwrap().is_none());
+}
+
+#[test]
+fn test_response_round_trip() {
+    let mut raw: Vec<u8> = vec![];
+    Response::build(Command::Version).value(Some("1.5.0")).finish().unwrap().write_to(&mut raw).unwrap();
+    Response::build(Command::Get).status(Status::KeyNotFound).finish().unwrap().write_to(&mut raw).unwrap();
+
+    let mut src = Chunked { data: io::Cursor::new(raw), interrupted: false };
+    let first = Response::read_from(&mut src).unwrap().unwrap();
+    let second = Response::read_from(&mut src).unwrap().unwrap();
+
+    assert_eq!(first.value(), Some(&b"1.5.0"[..]));
+    assert_eq!(*second.status(), Status::KeyNotFound);
+    assert!(Response::read_from(&mut src).unwrap().is_none());
+}
+
+#[test]
+fn test_truncated_header() {
+    let raw = vec![0x81, 0x00, 0x00];
+    assert_truncated(Response::read_from(&mut io::Cursor::new(raw)));
+}
+
+#[test]
+fn test_truncated_body() {
+    let mut raw: Vec<u8> = vec![];
+    Response::build(Command::Version).value(Some("1.5.0")).finish().unwrap().write_to(&mut raw).unwrap();
+    raw.truncate(26);
+
+    assert_truncated(Response::read_from(&mut io::Cursor::new(raw)));
+}
+
+#[test]
+fn test_request_magic() {
+    let mut raw: Vec<u8> = vec![];
+    Request::noop().write_to(&mut raw).unwrap();
+
+    assert!(Response::read_from(&mut io::Cursor::new(raw)).is_err());
+}
+
+#[test]
+fn test_request_limits() {
+    let mut raw: Vec<u8> = vec![];
+    Request::append(b"Hello", b"World").write_to(&mut raw).unwrap();
+
+    match Request::read_from_with_limit(&mut io::Cursor::new(&raw), 4) {
+        Err(Error::BodyLimitExceeded { length: 10, limit: 4 }) => {},
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert!(Request::read_from_with_limit(&mut io::Cursor::new(&raw), 10).unwrap().is_some());
+
+    // Garbage header with a huge body length is rejected before the body is read
+    let mut header = vec![0; 24];
+    header[0] = 0x80;
+    header[8..12].copy_from_slice(&[0xff, 0xff, 0xff, 0xff]);
+    let mut src = io::Cursor::new(header);
+    match Request::read_from(&mut src) {
+        Err(Error::BodyLimitExceeded { length: 0xffffffff, limit: DEFAULT_MAX_BODY_LENGTH }) => {},
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert_eq!(src.position(), 24);
+
+    // Response magic is rejected before the body is read
+    let mut raw: Vec<u8> = vec![];
+    Response::build(Command::Version).value(Some("1.5.0")).finish().unwrap().write_to(&mut raw).unwrap();
+    let mut src = io::Cursor::new(raw);
+    assert!(Request::read_from(&mut src).is_err());
+    assert_eq!(src.position(), 24);
+}
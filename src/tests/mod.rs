@@ -1 +1,13 @@
-mod response;
\ No newline at end of file
+mod response;
+mod response_builder;
+mod response_decoder;
+mod response_extras;
+mod command;
+mod tap;
+mod range;
+mod stats;
+mod framing;
+#[cfg(feature = "tokio")]
+mod codec;
+#[cfg(feature = "futures")]
+mod async_io;
@@ -0,0 +1,63 @@
+use ::{Command, Response, RangeCollector, Status, Error};
+use extras;
+
+#[test]
+fn test_collect() {
+    let mut collector = RangeCollector::new();
+
+    let first = Response::build(Command::RGet)
+        .extras(Some(extras::Get::new(0xdeadbeef))).unwrap()
+        .key(Some("a"))
+        .value(Some("1"))
+        .finish().unwrap();
+    let second = Response::build(Command::RGet).key(Some("b")).finish().unwrap();
+    let end = Response::build(Command::RGet).finish().unwrap();
+
+    assert!(!collector.push(first).unwrap());
+    assert!(!collector.push(second).unwrap());
+    assert!(!collector.is_done());
+    assert!(collector.push(end).unwrap());
+    assert!(collector.is_done());
+
+    // Stream is already terminated
+    let extra = Response::build(Command::RGet).key(Some("c")).finish().unwrap();
+    match collector.push(extra) {
+        Err(Error::UnexpectedCommand(Command::RGet)) => {},
+        _ => panic!("Responses after the end should be rejected"),
+    }
+
+    let items = collector.into_items();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].key(), b"a");
+    assert_eq!(items[0].value(), Some(&b"1"[..]));
+    assert_eq!(items[0].flags(), Some(0xdeadbeef));
+    assert_eq!(items[1].key(), b"b");
+    assert_eq!(items[1].value(), None);
+    assert_eq!(items[1].flags(), None);
+}
+
+#[test]
+fn test_empty_range() {
+    let mut collector = RangeCollector::new();
+    let response = Response::build(Command::RGet).status(Status::KeyNotFound).finish().unwrap();
+
+    assert!(collector.push(response).unwrap());
+    assert!(collector.items().is_empty());
+}
+
+#[test]
+fn test_errors() {
+    let mut collector = RangeCollector::new();
+
+    let response = Response::build(Command::Get).key(Some("a")).finish().unwrap();
+    match collector.push(response) {
+        Err(Error::UnexpectedCommand(Command::Get)) => {},
+        _ => panic!("Only RGet responses are expected"),
+    }
+
+    let response = Response::build(Command::RGet).status(Status::OutOfMemory).finish().unwrap();
+    match collector.push(response) {
+        Err(Error::Status(Status::OutOfMemory)) => {},
+        _ => panic!("Error status should be reported"),
+    }
+}
@@ -1,7 +1,10 @@
 #[cfg(feature = "nightly")]
 use test::Bencher;
 
-use ::{Command, Status, DataType, Response};
+use std::io;
+use std::error;
+
+use ::{Command, Status, DataType, Response, Error};
 
 #[test]
 fn test_response_get() {
@@ -28,6 +31,28 @@ fn test_response_get() {
     assert_eq!(response.value().unwrap(), b"World");
 }
 
+#[test]
+fn test_response_touch() {
+    // Successful Touch reply from memcached: flags extras, no value
+    let buf: Vec<u8> = vec![
+        0x81, 0x1c, 0x00, 0x00,
+        0x04, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x04,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x01,
+        0xde, 0xad, 0xbe, 0xef,
+    ];
+
+    let response = Response::read(&buf).unwrap().unwrap();
+
+    assert_eq!(*response.command(), Command::Touch);
+    assert_eq!(*response.status(), Status::Ok);
+    assert_eq!(response.flags().unwrap(), 0xdeadbeef);
+    assert!(response.key().is_none());
+    assert!(response.value().is_none());
+}
+
 #[test]
 fn test_response_getk() {
     // I dunno why, but memcached doc have a mistake here,
@@ -170,3 +195,70 @@ fn bench_parsing_response_get(b: &mut Bencher) {
 
     b.iter(|| Response::try_from(&buf));
 }
+
+#[test]
+fn test_response_error_status() {
+    let buf: Vec<u8> = vec![
+        0x81, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x01,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let response = Response::read(&buf).unwrap().unwrap();
+    let error = response.status().into_result().unwrap_err();
+
+    assert_eq!(error.to_string(), "server responded with error: key not found");
+    match error {
+        Error::Status(Status::KeyNotFound) => {},
+        _ => panic!("Unexpected error: {:?}", error),
+    }
+}
+
+#[test]
+fn test_response_error_display() {
+    let mut buf: Vec<u8> = vec![0x42; 24];
+    buf[8..12].copy_from_slice(&[0, 0, 0, 0]);
+
+    let error = Response::read(&buf).unwrap_err();
+    assert_eq!(error.to_string(), "unknown magic byte 0x42");
+    assert!(format!("{:?}", error).contains("UnknownMagic"));
+
+    let boxed: Box<dyn error::Error + Send + Sync> = Box::new(error);
+    assert!(boxed.source().is_none());
+}
+
+#[test]
+fn test_io_error_source() {
+    let error = Error::from(io::Error::new(io::ErrorKind::UnexpectedEof, "eof"));
+
+    assert_eq!(error.to_string(), "I/O error: eof");
+    assert!(error::Error::source(&error).is_some());
+}
+
+#[test]
+fn test_response_unknown_values() {
+    let buf: Vec<u8> = vec![
+        0x81, 0x1f, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x09,
+        0x00, 0x00, 0x00, 0x04,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x01, 0x02, 0x03, 0x04,
+    ];
+
+    let response = Response::read(&buf).unwrap().unwrap();
+    assert_eq!(*response.command(), Command::Unknown(0x1f));
+    assert_eq!(*response.status(), Status::Unknown(0x09));
+    assert_eq!(response.extras(), Some(&[0x01, 0x02][..]));
+    assert_eq!(Status::Unknown(0x09).to_string(), "unknown status 0x0009");
+    assert_eq!(Status::from(0x0020), Status::AuthenticationError);
+    assert_eq!(Status::from(0x0021), Status::AuthenticationContinue);
+
+    let mut raw: Vec<u8> = vec![];
+    response.write(&mut raw).unwrap();
+    assert_eq!(raw, buf);
+}
@@ -0,0 +1,103 @@
+use std::io;
+
+use ::{Command, Status, Response, Error};
+use extras;
+
+fn serialize(response: &Response) -> Vec<u8> {
+    let mut result: Vec<u8> = vec![];
+    response.write(&mut result).unwrap();
+    result
+}
+
+#[test]
+fn test_response_getk() {
+    let expected: Vec<u8> = vec![
+        0x81, 0x0c, 0x00, 0x05,
+        0x04, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x0e,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x01,
+        0xde, 0xad, 0xbe, 0xef,
+        0x48, 0x65, 0x6c, 0x6c,
+        0x6f, 0x57, 0x6f, 0x72,
+        0x6c, 0x64,
+    ];
+
+    let response = Response::build(Command::GetK)
+        .extras(Some(extras::Get::new(0xdeadbeef))).unwrap()
+        .key(Some("Hello"))
+        .value(Some("World"))
+        .cas(1)
+        .finish()
+        .unwrap();
+
+    assert_eq!(serialize(&response), expected);
+    assert_eq!(response.len(), expected.len());
+}
+
+#[test]
+fn test_response_error_not_found() {
+    let expected: Vec<u8> = vec![
+        0x81, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x01,
+        0x00, 0x00, 0x00, 0x09,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x4e, 0x6f, 0x74, 0x20,
+        0x66, 0x6f, 0x75, 0x6e,
+        0x64,
+    ];
+
+    let response = Response::build(Command::Get)
+        .status(Status::KeyNotFound)
+        .value(Some("Not found"))
+        .finish()
+        .unwrap();
+
+    assert_eq!(serialize(&response), expected);
+}
+
+#[test]
+fn test_round_trip() {
+    let response = Response::build(Command::Increment)
+        .opaque(0xcafe)
+        .cas(5)
+        .value(Some([0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x2a]))
+        .finish()
+        .unwrap();
+    let raw = serialize(&response);
+
+    let parsed = Response::read(&raw).unwrap().unwrap();
+    assert_eq!(*parsed.command(), Command::Increment);
+    assert_eq!(*parsed.status(), Status::Ok);
+    assert_eq!(*parsed.opaque(), 0xcafe);
+    assert_eq!(*parsed.cas(), 5);
+    assert!(parsed.extras().is_none());
+    assert!(parsed.key().is_none());
+    assert_eq!(parsed.value().unwrap(), [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x2a]);
+    assert_eq!(serialize(&parsed), raw);
+}
+
+#[test]
+fn test_key_too_long() {
+    let result = Response::build(Command::GetK)
+        .key(Some(vec![0x61; 70 * 1024]))
+        .finish();
+
+    match result {
+        Err(Error::KeyTooLong(length)) => assert_eq!(length, 70 * 1024),
+        _ => panic!("Key length should be rejected"),
+    }
+}
+
+#[test]
+fn test_not_enough_capacity() {
+    let response = Response::build(Command::Noop).finish().unwrap();
+
+    let mut raw = [0u8; 16];
+    let mut out = io::Cursor::new(&mut raw[..]);
+    assert!(response.write(&mut out).is_err());
+    assert_eq!(out.position(), 0);
+}
@@ -0,0 +1,223 @@
+use bytes::BytesMut;
+
+use ::{Command, Status, Response, ResponseDecoder, Error};
+use extras;
+
+fn pipelined() -> Vec<u8> {
+    vec![
+        // GetK "Hello" -> "World" with flags
+        0x81, 0x0c, 0x00, 0x05,
+        0x04, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x0e,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x01,
+        0xde, 0xad, 0xbe, 0xef,
+        0x48, 0x65, 0x6c, 0x6c,
+        0x6f, 0x57, 0x6f, 0x72,
+        0x6c, 0x64,
+        // Noop
+        0x81, 0x0a, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        // Get miss
+        0x81, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x01,
+        0x00, 0x00, 0x00, 0x09,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x4e, 0x6f, 0x74, 0x20,
+        0x66, 0x6f, 0x75, 0x6e,
+        0x64,
+    ]
+}
+
+fn check(responses: &[Response]) {
+    assert_eq!(responses.len(), 3);
+
+    assert_eq!(*responses[0].command(), Command::GetK);
+    assert_eq!(responses[0].extras().unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+    assert_eq!(responses[0].key().unwrap(), b"Hello");
+    assert_eq!(responses[0].value().unwrap(), b"World");
+
+    assert_eq!(*responses[1].command(), Command::Noop);
+    assert!(responses[1].value().is_none());
+
+    assert_eq!(*responses[2].command(), Command::Get);
+    assert_eq!(*responses[2].status(), Status::KeyNotFound);
+    assert_eq!(responses[2].value().unwrap(), b"Not found");
+}
+
+#[test]
+fn test_pipelined() {
+    let mut buf = BytesMut::from(pipelined());
+    let mut decoder = ResponseDecoder::new();
+
+    let mut responses = vec![];
+    while let Some(response) = decoder.decode(&mut buf).unwrap() {
+        responses.push(response);
+    }
+
+    check(&responses);
+    assert!(buf.is_empty());
+    assert!(!decoder.is_pending());
+}
+
+#[test]
+fn test_fragmented_at_every_boundary() {
+    let raw = pipelined();
+
+    for split in 0..raw.len() + 1 {
+        let mut buf = BytesMut::with_capacity(raw.len());
+        let mut decoder = ResponseDecoder::new();
+        let mut responses = vec![];
+
+        for chunk in &[&raw[..split], &raw[split..]] {
+            buf.extend_from_slice(chunk);
+            while let Some(response) = decoder.decode(&mut buf).unwrap() {
+                responses.push(response);
+            }
+        }
+
+        check(&responses);
+        assert!(buf.is_empty());
+    }
+}
+
+#[test]
+fn test_byte_by_byte() {
+    let raw = pipelined();
+    let mut buf = BytesMut::with_capacity(raw.len());
+    let mut decoder = ResponseDecoder::new();
+    let mut responses = vec![];
+
+    for byte in &raw {
+        buf.extend_from_slice(&[*byte]);
+        while let Some(response) = decoder.decode(&mut buf).unwrap() {
+            responses.push(response);
+        }
+    }
+
+    check(&responses);
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn test_invalid_magic() {
+    let mut raw = pipelined();
+    raw[0] = 0x42;
+    let mut buf = BytesMut::from(raw);
+
+    match ResponseDecoder::new().decode(&mut buf) {
+        Err(Error::UnknownMagic(0x42)) => {},
+        _ => panic!("Invalid magic should be rejected"),
+    }
+    // Stream is out of sync, nothing is consumed
+    assert_eq!(buf.len(), pipelined().len());
+}
+
+#[test]
+fn test_rejected_response_skipped() {
+    // Successful `Get` response without flags, followed by the valid responses
+    let mut raw: Vec<u8> = vec![];
+    Response::build(Command::Get).value(Some("World")).finish().unwrap().write(&mut raw).unwrap();
+    raw.extend_from_slice(&pipelined());
+
+    let mut decoder = ResponseDecoder::new();
+    let mut buf = BytesMut::from(raw.clone());
+    match decoder.decode(&mut buf) {
+        Err(Error::InvalidLength { expected: 4, actual: 0 }) => {},
+        _ => panic!("Missing flags should be rejected"),
+    }
+    let mut responses = vec![];
+    while let Some(response) = decoder.decode(&mut buf).unwrap() {
+        responses.push(response);
+    }
+    check(&responses);
+
+    // Body of the rejected response is dropped even if it arrives later
+    let mut decoder = ResponseDecoder::new();
+    let mut buf = BytesMut::from(&raw[..26]);
+    assert!(decoder.decode(&mut buf).is_err());
+    assert!(decoder.is_pending());
+    assert!(decoder.decode(&mut buf).unwrap().is_none());
+    assert!(buf.is_empty());
+
+    buf.extend_from_slice(&raw[26..]);
+    let mut responses = vec![];
+    while let Some(response) = decoder.decode(&mut buf).unwrap() {
+        responses.push(response);
+    }
+    check(&responses);
+}
+
+#[test]
+fn test_truncated_body() {
+    let raw = pipelined();
+
+    for length in 0..38 {
+        assert!(Response::read(&raw[..length]).unwrap().is_none());
+    }
+    assert!(Response::read(&raw[..38]).unwrap().is_some());
+}
+
+#[test]
+fn test_invalid_body_length() {
+    let mut raw = pipelined();
+    raw[11] = 0x02;
+
+    match Response::read(&raw) {
+        Err(Error::InvalidBodyLength(2)) => {},
+        _ => panic!("Body length should be validated"),
+    }
+}
+
+#[test]
+fn test_zero_copy_body() {
+    let value = vec![0x61; 1024];
+    let mut raw: Vec<u8> = vec![];
+    Response::build(Command::GetK)
+        .extras(Some(extras::Get::new(0))).unwrap()
+        .key(Some("Hello"))
+        .value(Some(&value))
+        .finish().unwrap()
+        .write(&mut raw).unwrap();
+    raw.extend_from_slice(&pipelined());
+
+    let mut buf = BytesMut::from(raw);
+    let start = buf.as_ptr() as usize;
+
+    let response = Response::read_bytes(&mut buf).unwrap().unwrap();
+    assert_eq!(response.key_bytes().unwrap(), &b"Hello"[..]);
+    let body = response.value_bytes().unwrap();
+    assert_eq!(body, &value[..]);
+    // Value is pointing into the original receive buffer
+    assert_eq!(body.as_ptr() as usize, start + 33);
+
+    let mut decoder = ResponseDecoder::new();
+    let mut responses = vec![];
+    while let Some(response) = decoder.decode(&mut buf).unwrap() {
+        responses.push(response);
+    }
+    check(&responses);
+}
+
+#[test]
+fn test_command_requirements() {
+    // Successful `Get` response without flags
+    let mut raw: Vec<u8> = vec![];
+    Response::build(Command::Get).value(Some("World")).finish().unwrap().write(&mut raw).unwrap();
+
+    match Response::read(&raw) {
+        Err(Error::InvalidLength { expected: 4, actual: 0 }) => {},
+        _ => panic!("Missing flags should be rejected"),
+    }
+
+    // Error responses are not checked
+    raw[7] = 0x01;
+    assert!(Response::read(&raw).unwrap().is_some());
+}
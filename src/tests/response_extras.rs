@@ -0,0 +1,145 @@
+use ::{Command, Response, Error, VBucketState};
+use extras;
+
+#[test]
+fn test_extras_as_get() {
+    let buf: Vec<u8> = vec![
+        0x81, 0x00, 0x00, 0x00,
+        0x04, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x09,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x01,
+        0xde, 0xad, 0xbe, 0xef,
+        0x57, 0x6f, 0x72, 0x6c,
+        0x64,
+    ];
+
+    let response = Response::read(&buf).unwrap().unwrap();
+
+    assert_eq!(response.extras_as::<extras::Get>().unwrap().flags(), 0xdeadbeef);
+    assert_eq!(response.flags().unwrap(), 0xdeadbeef);
+}
+
+#[test]
+fn test_extras_as_wrong_command() {
+    let response = Response::build(Command::Set).finish().unwrap();
+
+    match response.extras_as::<extras::Get>() {
+        Err(Error::InvalidExtras(Command::Set)) => {},
+        _ => panic!("Set responses have no flags"),
+    }
+    match response.extras_as::<extras::Set>() {
+        Err(Error::InvalidExtras(Command::Set)) => {},
+        _ => panic!("Set extras are used in requests only"),
+    }
+}
+
+#[test]
+fn test_flags_missing() {
+    let response = Response::build(Command::GetK).key(Some("Hello")).finish().unwrap();
+
+    match response.flags() {
+        Err(Error::InvalidLength { expected: 4, actual: 0 }) => {},
+        _ => panic!("Missing flags should be reported"),
+    }
+}
+
+#[test]
+fn test_flags_truncated() {
+    // Extras length is not validated for error responses
+    let buf: Vec<u8> = vec![
+        0x81, 0x00, 0x00, 0x00,
+        0x02, 0x00, 0x00, 0x01,
+        0x00, 0x00, 0x00, 0x02,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0xde, 0xad,
+    ];
+
+    let response = Response::read(&buf).unwrap().unwrap();
+
+    match response.flags() {
+        Err(Error::InvalidLength { expected: 4, actual: 2 }) => {},
+        _ => panic!("Truncated flags should be reported"),
+    }
+}
+
+#[test]
+fn test_counter() {
+    let buf: Vec<u8> = vec![
+        0x81, 0x05, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x08,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x05,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x01, 0x00,
+    ];
+
+    let response = Response::read(&buf).unwrap().unwrap();
+
+    assert_eq!(response.counter().unwrap(), 256);
+}
+
+#[test]
+fn test_counter_errors() {
+    let response = Response::build(Command::Get).value(Some([0u8; 8])).finish().unwrap();
+    match response.counter() {
+        Err(Error::UnexpectedCommand(Command::Get)) => {},
+        _ => panic!("Get responses have no counter"),
+    }
+
+    let response = Response::build(Command::Decrement).value(Some("Non-numeric")).finish().unwrap();
+    match response.counter() {
+        Err(Error::InvalidLength { expected: 8, actual: 11 }) => {},
+        _ => panic!("Counter length should be checked"),
+    }
+}
+
+#[test]
+fn test_vbucket_state() {
+    let buf: Vec<u8> = vec![
+        0x81, 0x3e, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x04,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x02,
+    ];
+
+    let response = Response::read(&buf).unwrap().unwrap();
+
+    assert_eq!(response.vbucket_state().unwrap(), VBucketState::Replica);
+}
+
+#[test]
+fn test_vbucket_state_errors() {
+    let response = Response::build(Command::Get).value(Some([0, 0, 0, 1])).finish().unwrap();
+    match response.vbucket_state() {
+        Err(Error::UnexpectedCommand(Command::Get)) => {},
+        _ => panic!("Get responses have no vBucket state"),
+    }
+
+    let response = Response::build(Command::GetVBucket).value(Some([0, 0, 0, 9])).finish().unwrap();
+    match response.vbucket_state() {
+        Err(Error::UnknownVBucketState(9)) => {},
+        _ => panic!("Unknown states should be reported"),
+    }
+
+    let response = Response::build(Command::GetVBucket).finish().unwrap();
+    match response.vbucket_state() {
+        Err(Error::InvalidLength { expected: 4, actual: 0 }) => {},
+        _ => panic!("State length should be checked"),
+    }
+}
+
+#[test]
+fn test_builder_rejects_request_extras() {
+    let result = Response::build(Command::Set).extras(Some(extras::Set::default()));
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+
+use ::{Command, Request, Response, StatsCollector, StatGroup, Status, Error};
+
+fn stat(key: &str, value: &str) -> Response {
+    Response::build(Command::Stat).key(Some(key)).value(Some(value)).finish().unwrap()
+}
+
+#[test]
+fn test_collect() {
+    let mut collector = StatsCollector::new();
+
+    assert!(!collector.push(stat("curr_items", "42")).unwrap());
+    assert!(!collector.push(stat("get_hits", "1024")).unwrap());
+    assert!(!collector.push(stat("evictions", "0")).unwrap());
+    assert!(!collector.push(stat("version", "1.5.0")).unwrap());
+    assert!(!collector.is_done());
+    assert!(collector.push(Response::build(Command::Stat).finish().unwrap()).unwrap());
+    assert!(collector.is_done());
+
+    // Stream is already terminated
+    match collector.push(stat("curr_items", "43")) {
+        Err(Error::UnexpectedCommand(Command::Stat)) => {},
+        _ => panic!("Responses after the end should be rejected"),
+    }
+
+    let stats = collector.into_stats();
+    assert_eq!(stats.len(), 4);
+    assert_eq!(stats.curr_items(), Some(42));
+    assert_eq!(stats.get_hits(), Some(1024));
+    assert_eq!(stats.evictions(), Some(0));
+    assert_eq!(stats.get_misses(), None);
+    assert_eq!(stats.get("version"), Some("1.5.0"));
+    assert_eq!(stats.get_u64("version"), None);
+
+    let map: HashMap<String, String> = stats.into();
+    assert_eq!(map["curr_items"], "42");
+}
+
+#[test]
+fn test_errors() {
+    let mut collector = StatsCollector::new();
+
+    match collector.push(Response::build(Command::Version).finish().unwrap()) {
+        Err(Error::UnexpectedCommand(Command::Version)) => {},
+        _ => panic!("Only Stat responses are expected"),
+    }
+
+    let response = Response::build(Command::Stat).status(Status::UnknownCommand).finish().unwrap();
+    match collector.push(response) {
+        Err(Error::Status(Status::UnknownCommand)) => {},
+        _ => panic!("Error status should be reported"),
+    }
+}
+
+#[test]
+fn test_groups() {
+    assert_eq!(Request::stat(Some(StatGroup::Settings)).key(), Some(&b"settings"[..]));
+    assert_eq!(Request::stat(Some(StatGroup::Slabs)).key(), Some(&b"slabs"[..]));
+    assert_eq!(Request::stat(Some(StatGroup::Conns)).key(), Some(&b"conns"[..]));
+    assert_eq!(Request::stat(Some(StatGroup::DetailDump)).key(), Some(&b"detail dump"[..]));
+    assert_eq!(StatGroup::Items.to_string(), "items");
+}
@@ -0,0 +1,106 @@
+use ::{Command, Request, TapMessage, TapMessageFlags, VBucketState, Error};
+use extras::Expiry;
+
+fn message(raw: &[u8]) -> Result<TapMessage, Error> {
+    TapMessage::from_request(&Request::read(raw).unwrap().unwrap())
+}
+
+#[test]
+fn test_mutation() {
+    let raw: Vec<u8> = vec![
+        0x80, 0x41, 0x00, 0x05,
+        0x10, 0x00, 0x00, 0x03,
+        0x00, 0x00, 0x00, 0x1d,
+        0x00, 0x00, 0x00, 0x01,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x09,
+        0x00, 0x03, 0x00, 0x05,
+        0x02, 0x00, 0x00, 0x00,
+        0xde, 0xad, 0xbe, 0xef,
+        0x00, 0x00, 0x0e, 0x10,
+        0x01, 0x02, 0x03, 0x48,
+        0x65, 0x6c, 0x6c, 0x6f,
+        0x57, 0x6f, 0x72, 0x6c,
+        0x64,
+    ];
+
+    let message = message(&raw).unwrap();
+    assert_eq!(message.command(), Command::TapMutation);
+    assert_eq!(message.vbucket_id(), 3);
+    assert_eq!(message.opaque(), 1);
+    assert_eq!(message.cas(), 9);
+    assert_eq!(message.engine_private(), &[0x01, 0x02, 0x03]);
+    assert!(message.flags().contains(TapMessageFlags::ACK));
+    assert!(message.flags().contains(TapMessageFlags::NETWORK_BYTE_ORDER));
+    assert!(!message.flags().contains(TapMessageFlags::NO_VALUE));
+    assert_eq!(message.ttl(), 2);
+    assert_eq!(message.item_flags(), Some(0xdeadbeef));
+    assert_eq!(message.expiration(), Some(Expiry::from_seconds(3600)));
+    assert_eq!(message.key(), Some(&b"Hello"[..]));
+    assert_eq!(message.value(), Some(&b"World"[..]));
+}
+
+#[test]
+fn test_vbucket_set() {
+    let raw: Vec<u8> = vec![
+        0x80, 0x45, 0x00, 0x00,
+        0x08, 0x00, 0x00, 0x0c,
+        0x00, 0x00, 0x00, 0x0c,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x01, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x01,
+    ];
+
+    let message = message(&raw).unwrap();
+    assert!(message.engine_private().is_empty());
+    assert_eq!(message.key(), None);
+    assert_eq!(message.item_flags(), None);
+    assert_eq!(message.vbucket_state().unwrap(), VBucketState::Active);
+}
+
+#[test]
+fn test_unexpected_command() {
+    match TapMessage::from_request(&Request::noop()) {
+        Err(Error::UnexpectedCommand(Command::Noop)) => {},
+        _ => panic!("Noop is not a TAP message"),
+    }
+}
+
+#[test]
+fn test_short_extras() {
+    let raw: Vec<u8> = vec![
+        0x80, 0x41, 0x00, 0x00,
+        0x08, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x08,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x01, 0x00, 0x00, 0x00,
+    ];
+
+    match message(&raw) {
+        Err(Error::InvalidLength { expected: 16, actual: 8 }) => {},
+        _ => panic!("Mutation extras are 16 bytes long"),
+    }
+}
+
+#[test]
+fn test_engine_private_overflow() {
+    let raw: Vec<u8> = vec![
+        0x80, 0x44, 0x00, 0x00,
+        0x08, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x0a,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00,
+        0x00, 0x04, 0x00, 0x00,
+        0x01, 0x00, 0x00, 0x00,
+        0x01, 0x02,
+    ];
+
+    assert!(message(&raw).is_err());
+}